@@ -11,6 +11,8 @@ struct CsvTag {
 
     #[serde(deserialize_with = "from_hex")]
     element: u32,
+    /// Empty for the tags that have no VR (items and delimiters).
+    vr: String,
     multiplicity: i8,
     name: String,
     description: String,
@@ -26,13 +28,19 @@ where
 }
 
 impl CsvTag {
-    fn to_macro_line(self) -> String {
+    fn into_macro_line(self) -> String {
+        let vr = if self.vr.is_empty() {
+            "None".to_string()
+        } else {
+            format!("Some(ValueRepresentation::{})", self.vr)
+        };
         format!(
-            "(x{:04X}x{:04X}, {:#04X}, {:#04X}, {}, \"{}\", \"{}\")",
+            "(x{:04X}x{:04X}, {:#04X}, {:#04X}, {}, {}, \"{}\", \"{}\")",
             self.group,
             self.element,
             self.group,
             self.element,
+            vr,
             self.multiplicity,
             self.name,
             self.description
@@ -51,14 +59,13 @@ fn main() {
 
     let lines: Vec<String> = rdr
         .deserialize()
-        .into_iter()
         .map(|row| {
             let row: CsvTag = row.unwrap();
-            row.to_macro_line()
+            row.into_macro_line()
         })
         .collect();
     macro_str.push_str(&lines.join(","));
-    macro_str.push_str("}");
+    macro_str.push('}');
 
     fs::write(dest_path, macro_str).unwrap();
     println!("cargo:rerun-if-changed=build.rs");
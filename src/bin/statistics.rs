@@ -1,70 +1,90 @@
-use std::fs;
-use std::fs::{File};
-use std::path::Path;
-use nom::lib::std::fmt::{Formatter, Error};
-use std::io::Read;
+//! Dump some tags of all the DICOM files of a folder as CSV.
+//!
+//! Usage: statistics <folder> [tag...]
+//!
+//! Tags are given either by keyword ("Bits Allocated") or as "gggg,eeee" in hexadecimal. Missing
+//! tags are written as empty cells. Only the `.dcm` files are parsed.
+use dicom::parser::obj::Parser;
 use dicom::Tag;
-use std::any::Any;
 
-struct Results {
-    number_of_frames: i32,
-    bits_allocated: u16,
-    bits_stored: u16,
-    window_center: String,
-    window_width: String,
-}
+/// Tags that are dumped when none is given.
+const DEFAULT_TAGS: [&str; 5] = [
+    "Number of frames",
+    "Bits Allocated",
+    "Bits Stored",
+    "Window Center",
+    "Window Width",
+];
 
-impl std::fmt::Display for Results {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "{},{},{},{},{}", self.number_of_frames, self.bits_allocated, self.bits_stored, self.window_center, self.window_width)
+fn parse_tag(arg: &str) -> Result<Tag, String> {
+    let hex: Vec<_> = arg.split(',').collect();
+    if let [group, element] = hex[..] {
+        if let (Ok(group), Ok(element)) = (
+            u16::from_str_radix(group.trim(), 16),
+            u16::from_str_radix(element.trim(), 16),
+        ) {
+            return Ok(Tag::from_values(group, element));
+        }
     }
-}
-
-fn get_results<P: AsRef<Path>>(path: P) -> Result<Results, String> {
-    let mut file = File::open(path).unwrap();
-    let mut content = vec![];
-    file.read_to_end(&mut content).unwrap();
-
-    let mut parser = dicom::parser::obj::Parser::new(false);
-    let res = parser.parse_object(&content);
-    let obj = res.map_err(|e| format!("{}", e))?;
 
-    let number_of_frames = obj.try_get::<i32>(Tag::x0028x0008).unwrap_or(1);
-    let bits_allocated = obj.try_get::<u16>(Tag::x0028x0100).map_err(|e| format!("{}", e))?;
-    let bits_stored = obj.try_get::<u16>(Tag::x0028x0101).map_err(|e| format!("{}", e))?;
-    let window_center = obj.try_get::<String>(Tag::x0028x1050).map_err(|e| format!("{}", e))?;
-    let window_width = obj.try_get::<String>(Tag::x0028x1051).map_err(|e| format!("{}", e))?;
+    Tag::lookup_by_kw(arg).ok_or_else(|| format!("Unknown tag {}", arg))
+}
 
-    Ok(Results {
-        window_width, window_center,
-        bits_stored,
-        bits_allocated, number_of_frames
-    })
+/// Quote the cell if needed.
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 fn main() {
-    let dir_name = std::env::args().nth(1).unwrap();
-    println!("Will parse folder {}", dir_name);
+    let mut args = std::env::args().skip(1);
+    let dir_name = match args.next() {
+        Some(dir_name) => dir_name,
+        None => {
+            eprintln!("Usage: statistics <folder> [tag...]");
+            std::process::exit(1);
+        }
+    };
 
-    let mut results = vec![];
-    let mut errors = vec![];
-    for file in fs::read_dir(dir_name).unwrap() {
-        if let Ok(entry) = file {
-            if let Ok(ft) = entry.file_type() {
-                if ft.is_file() && !entry.path().ends_with("zip") {
-                    match get_results(entry.path()) {
-                        Ok(r) => results.push(r),
-                        Err(e) => errors.push(e)
-                    }
+    let mut names: Vec<String> = args.collect();
+    if names.is_empty() {
+        names = DEFAULT_TAGS.iter().map(|name| name.to_string()).collect();
+    }
+    let tags = match names.iter().map(|name| parse_tag(name)).collect::<Result<Vec<_>, _>>() {
+        Ok(tags) => tags,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let files = match dicom::parse_dir_with(&dir_name, Parser::new(false)) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Cannot read folder {} = {}", dir_name, e);
+            std::process::exit(1);
+        }
+    };
+
+    let header: Vec<_> = names.iter().map(|name| csv_cell(name)).collect();
+    println!("file,{}", header.join(","));
+    for (path, obj) in files {
+        match obj {
+            Ok(obj) => {
+                let mut row = vec![csv_cell(&path.display().to_string())];
+                for tag in &tags {
+                    let value = obj
+                        .get_element(*tag)
+                        .map(|el| el.to_display_string(&obj.transfer_syntax))
+                        .unwrap_or_default();
+                    row.push(csv_cell(&value));
                 }
+                println!("{}", row.join(","));
             }
+            Err(e) => eprintln!("Cannot parse {} = {}", path.display(), e),
         }
     }
-
-    println!("RESULTS\nNumber of frames,Bits allocated, bits stored, window center, window width");
-    for r in results {
-        println!("{}", r);
-    }
-
-    println!("Errors:\n{:?}", errors);
-}
\ No newline at end of file
+}
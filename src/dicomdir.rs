@@ -0,0 +1,134 @@
+//! A DICOMDIR is the index of the files of a media (CD, DVD...). Its Directory Record Sequence
+//! (x0004x1220) contains one item per record (patient, study, series, image...).
+//!
+//! The records form a tree: the offsets are the positions in the DICOMDIR file of the next record
+//! of the same level and of the first record of the level below.
+use crate::types::trim_padding;
+use crate::{DicomError, DicomObject, DicomResult, Tag};
+use std::path::PathBuf;
+
+/// A record of the Directory Record Sequence.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DirectoryRecord {
+    /// PATIENT, STUDY, SERIES, IMAGE...
+    pub record_type: String,
+    /// Path of the referenced file, relative to the DICOMDIR, one component per value. Only
+    /// records that reference a file have one.
+    pub referenced_file_id: Option<Vec<String>>,
+    /// Offset of the next record of the same level, 0 if it is the last one.
+    pub next_record_offset: u32,
+    /// Offset of the first record of the level below, 0 if there is none.
+    pub lower_level_offset: u32,
+}
+
+impl DirectoryRecord {
+    /// Return the path of the referenced file, relative to the folder of the DICOMDIR.
+    pub fn referenced_file_path(&self) -> Option<PathBuf> {
+        self.referenced_file_id
+            .as_ref()
+            .map(|components| components.iter().collect())
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the records of a DICOMDIR, in the order of the sequence.
+    pub fn directory_records(&self) -> DicomResult<Vec<DirectoryRecord>> {
+        let items = self
+            .get_element(Tag::x0004x1220)
+            .ok_or(DicomError::NoSuchTag(Tag::x0004x1220))?
+            .items()
+            .ok_or_else(|| {
+                DicomError::ParseError("Directory Record Sequence is not a sequence".to_string())
+            })?;
+
+        items
+            .iter()
+            .map(|item| {
+                let record_type: String = item.try_get(Tag::x0004x1430)?;
+                let referenced_file_id =
+                    match item.try_get::<String>(Tag::x0004x1500) {
+                        Ok(id) => Some(
+                            trim_padding(&id)
+                                .split('\\')
+                                .map(|c| c.trim().to_string())
+                                .collect(),
+                        ),
+                        Err(DicomError::NoSuchTag(_)) => None,
+                        Err(e) => return Err(e),
+                    };
+
+                Ok(DirectoryRecord {
+                    record_type: trim_padding(&record_type).to_string(),
+                    referenced_file_id,
+                    next_record_offset: item.try_get(Tag::x0004x1400)?,
+                    lower_level_offset: item.try_get(Tag::x0004x1420)?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::sq::Item;
+    use crate::test_helpers::element;
+    use crate::types::{DataElement, Value};
+    use crate::TransferSyntax;
+
+    fn record(record_type: &str, file_id: Option<&str>, next: u32, lower: u32) -> Item<'static> {
+        let mut elements = vec![
+            element(Tag::x0004x1400, next.to_le_bytes().to_vec()),
+            element(Tag::x0004x1420, lower.to_le_bytes().to_vec()),
+            element(Tag::x0004x1430, record_type.as_bytes().to_vec()),
+        ];
+        if let Some(file_id) = file_id {
+            elements.push(element(Tag::x0004x1500, file_id.as_bytes().to_vec()));
+        }
+        Item {
+            elements,
+            transfer_syntax: TransferSyntax::little_endian_explicit(),
+        }
+    }
+
+    #[test]
+    fn read_directory_records() {
+        let records = DataElement {
+            tag: Tag::x0004x1220,
+            vr: None,
+            length: u32::MAX,
+            data: Value::Sequence(vec![
+                record("PATIENT ", None, 0, 412),
+                record("IMAGE ", Some("DICOM\\PA1\\IM1 "), 0, 0),
+            ]),
+        };
+        let obj = DicomObject::new(vec![records], TransferSyntax::little_endian_explicit());
+
+        let records = obj.directory_records().unwrap();
+        assert_eq!(
+            vec![
+                DirectoryRecord {
+                    record_type: "PATIENT".to_string(),
+                    referenced_file_id: None,
+                    next_record_offset: 0,
+                    lower_level_offset: 412,
+                },
+                DirectoryRecord {
+                    record_type: "IMAGE".to_string(),
+                    referenced_file_id: Some(vec![
+                        "DICOM".to_string(),
+                        "PA1".to_string(),
+                        "IM1".to_string()
+                    ]),
+                    next_record_offset: 0,
+                    lower_level_offset: 0,
+                },
+            ],
+            records
+        );
+        assert_eq!(
+            Some(PathBuf::from("DICOM").join("PA1").join("IM1")),
+            records[1].referenced_file_path()
+        );
+    }
+}
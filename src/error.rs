@@ -1,6 +1,7 @@
 use crate::tag::Tag;
 use crate::ValueRepresentation;
 use std::convert::From;
+use nom::error::{ErrorKind, ParseError};
 use std::io;
 use thiserror::Error;
 
@@ -10,8 +11,8 @@ pub enum DicomError {
     #[error("Error while parsing = {0}")]
     ParseError(String),
 
-    #[error("Cannot read header")]
-    CannotReadHeader,
+    #[error("Cannot read header, expected at least 132 bytes but got {0}")]
+    CannotReadHeader(usize),
 
     #[error(transparent)]
     IoError(#[from] io::Error),
@@ -28,18 +29,47 @@ pub enum DicomError {
     #[error("Cannot convert VR DA to timestamp = {0}")]
     ParseDA(chrono::format::ParseError),
 
+    #[error("Cannot parse TM to time = {0:?}")]
+    ParseTM(String),
+
+    #[error("Cannot parse DT to timestamp = {0:?}")]
+    ParseDT(String),
+
     #[error("Cannot parse AS to Age = {0}")]
     ParseAS(String),
 
-    #[error(transparent)]
-    ParseIS(#[from] std::num::ParseIntError),
+    #[error("Cannot parse IS of {0} to integer = {1:?}")]
+    ParseIS(Tag, String),
+
+    #[error("Cannot parse DS of {0} to decimals = {1:?}")]
+    ParseDS(Tag, String),
+
+    #[error("Cannot decode RLE pixel data = {0}")]
+    RleDecode(String),
+
+    #[error("Image has {0} pixels but the buffer has {1}")]
+    ImageBufferSize(usize, usize),
+
+    #[error("Bits allocated not supported yet = {0}")]
+    BitsAllocatedNotSupported(u16),
+
+    #[error("Bits stored = {0} is not valid with bits allocated = {1}")]
+    InvalidBitsStored(u16, u16),
 
     #[error("Cannot save to PNG, image format is not supported")]
     ImageFormatNotSupported,
 
+    #[error("Region (x, y, width, height) = {0:?} is outside of the {1}x{2} image")]
+    RegionOutOfBounds((u32, u32, u32, u32), u32, u32),
+
+    #[cfg(feature = "image")]
     #[error(transparent)]
     ImageError(#[from] image::ImageError),
 
+    #[cfg(feature = "image")]
+    #[error(transparent)]
+    TiffError(#[from] tiff::TiffError),
+
     #[error("Missing Tag: {0}")]
     MissingTag(Tag),
 
@@ -54,11 +84,39 @@ pub enum DicomError {
 
     #[error("First group should be 0x0002 but got {0:?} instead")]
     ExpectedGroup2(Tag),
+
+    #[error("Sequences are nested too deeply")]
+    NestingTooDeep,
+
+    #[error("{0:?} goes beyond the end of its item")]
+    ItemLengthOverrun(Tag),
+
+    #[error("Undefined length is only allowed for sequences but {0:?} has one")]
+    UnexpectedUndefinedLength(Tag),
+
+    #[error("{0:?} has {2} bytes, too many for the length of VR {1:?}")]
+    ValueTooLong(Tag, ValueRepresentation, usize),
+}
+
+/// The parsers use `DicomError` as error so that they can report errors that are specific to DICOM.
+impl ParseError<&[u8]> for DicomError {
+    fn from_error_kind(input: &[u8], kind: ErrorKind) -> Self {
+        DicomError::ParseError(format!("{:?} with {} bytes left", kind, input.len()))
+    }
+
+    fn append(_input: &[u8], _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
 }
 
-impl<E> From<nom::Err<E>> for DicomError where E: std::fmt::Debug {
-    fn from(err: nom::Err<E>) -> Self {
-        DicomError::ParseError(format!("{}", err))
+impl From<nom::Err<DicomError>> for DicomError {
+    fn from(err: nom::Err<DicomError>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(needed) => {
+                DicomError::ParseError(format!("Incomplete data, needed {:?}", needed))
+            }
+        }
     }
 }
 
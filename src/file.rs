@@ -0,0 +1,138 @@
+//! Helpers to parse DICOM files from the file system.
+use crate::{DicomResult, OwnedDicomObject, Parser};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Read and parse a DICOM file. The object is owned so it can be returned without the content of
+/// the file.
+fn parse_file(path: &Path, parser: &mut Parser) -> DicomResult<OwnedDicomObject> {
+    let content = fs::read(path)?;
+    let obj = parser.parse_object(&content)?;
+    Ok(obj.into_owned())
+}
+
+/// Read and parse a DICOM file with the default parser, including the image.
+///
+/// ```rust,no_run
+/// use dicom::types::PersonName;
+/// use dicom::Tag;
+///
+/// let obj = dicom::open("file.dcm").unwrap();
+/// let name: PersonName = obj.get(Tag::x0010x0010);
+/// ```
+pub fn open<P: AsRef<Path>>(path: P) -> DicomResult<OwnedDicomObject> {
+    parse_file(path.as_ref(), &mut Parser::default())
+}
+
+fn is_dcm_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("dcm"))
+}
+
+/// Iterate over the `.dcm` files of a directory. The files are read and parsed lazily, one at a
+/// time, when the iterator is advanced.
+///
+/// ```rust,no_run
+/// for (path, obj) in dicom::parse_dir("some/folder").unwrap() {
+///     match obj {
+///         Ok(obj) => println!("{:?} has {} elements", path, obj.elements().len()),
+///         Err(e) => println!("Cannot parse {:?} = {}", path, e),
+///     }
+/// }
+/// ```
+pub fn parse_dir<P: AsRef<Path>>(
+    dir: P,
+) -> DicomResult<impl Iterator<Item = (PathBuf, DicomResult<OwnedDicomObject>)>> {
+    parse_dir_with(dir, Parser::default())
+}
+
+/// Same as `parse_dir` but the files are parsed with the given parser.
+pub fn parse_dir_with<P: AsRef<Path>>(
+    dir: P,
+    mut parser: Parser,
+) -> DicomResult<impl Iterator<Item = (PathBuf, DicomResult<OwnedDicomObject>)>> {
+    let entries = fs::read_dir(dir)?;
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_dcm_file(path))
+        .map(move |path| {
+            let obj = parse_file(&path, &mut parser);
+            (path, obj)
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_files::{explicit_le, fixture, temp_dir};
+    use crate::types::PersonName;
+    use crate::{DicomError, Tag};
+
+    #[test]
+    fn open_file() {
+        let dir = temp_dir("open");
+        let path = dir.join("file.dcm");
+        fs::write(&path, fixture("DOE^JOHN", &[])).unwrap();
+
+        let obj = open(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(vec!["DOE", "JOHN"], obj.get::<PersonName>(Tag::x0010x0010).0);
+        #[cfg(feature = "image")]
+        assert!(obj.image.is_some());
+    }
+
+    #[test]
+    fn parse_two_files() {
+        let dir = temp_dir("parse-dir");
+        fs::write(dir.join("first.dcm"), fixture("DOE^JOHN", &[])).unwrap();
+        fs::write(dir.join("second.DCM"), fixture("DOE^JANE", &[])).unwrap();
+        fs::write(dir.join("notes.txt"), b"not a dicom").unwrap();
+
+        let mut names: Vec<_> = parse_dir(&dir)
+            .unwrap()
+            .map(|(_, obj)| obj.unwrap().get::<PersonName>(Tag::x0010x0010).0.join(" "))
+            .collect();
+        names.sort();
+        assert_eq!(vec!["DOE JANE", "DOE JOHN"], names);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_dir_with_unsupported_files() {
+        let dir = temp_dir("parse-dir-unsupported");
+        fs::write(dir.join("valid.dcm"), fixture("DOE^JOHN", &[])).unwrap();
+        // JPEG baseline cannot be parsed, and a group 2 without transfer syntax is invalid.
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.4.50"));
+        // The data set of the fixture starts after its 28 bytes transfer syntax element.
+        content.extend_from_slice(&fixture("DOE^JANE", &[])[132 + 28..]);
+        fs::write(dir.join("jpeg.dcm"), content).unwrap();
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0013, "SH", b"dicom-rs"));
+        content.extend(explicit_le(0x0010, 0x0010, "PN", b"DOE^JIM "));
+        fs::write(dir.join("no-syntax.dcm"), content).unwrap();
+
+        let mut results: Vec<_> = parse_dir(&dir)
+            .unwrap()
+            .map(|(path, obj)| (path.file_name().unwrap().to_os_string(), obj))
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(3, results.len());
+        assert!(matches!(
+            results[0].1,
+            Err(DicomError::TransferSyntaxNotSupported(ref uid)) if uid == "1.2.840.10008.1.2.4.50"
+        ));
+        assert!(matches!(
+            results[1].1,
+            Err(DicomError::MissingTag(Tag::x0002x0010))
+        ));
+        assert!(results[2].1.is_ok());
+    }
+}
@@ -4,16 +4,126 @@
 //! so on. This should take care of it and return an ImageBuffer from the
 //! image crate, which can then be used to save the image to a file.
 //!
+//! Only the description of the pixels (geometry, photometric interpretation, windows...) and the
+//! stored values are available without the `image` feature.
+//!
+#[cfg(feature = "image")]
 use image::{ImageBuffer, Luma};
 
-use crate::error::DicomResult;
-use std::fmt;
-use std::path::Path;
-use std::fs::File;
-use std::io::Write;
+use crate::error::{DicomError, DicomResult};
+#[cfg(feature = "image")]
+use crate::parser::image::{parse_img_u16_into, parse_img_u8_into};
+use crate::parser::pixel_data::decode_rle_native;
+use crate::{DicomObject, Tag, TransferSyntax};
+use crate::types::{trim_padding, CompressionScheme, Value};
+use nom::number::Endianness;
+use std::borrow::Cow;
+#[cfg(feature = "image")]
+use std::{
+    fmt,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Photometric Interpretation (x0028x0004): how the samples of a pixel should be interpreted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PhotometricInterpretation {
+    /// Grayscale, the minimum value is white.
+    Monochrome1,
+    /// Grayscale, the minimum value is black.
+    Monochrome2,
+    Rgb,
+    /// The pixel value is an index in the palette color lookup tables.
+    PaletteColor,
+    YbrFull,
+    /// YCbCr with the chroma sampled horizontally at half the luma frequency.
+    YbrFull422,
+    Other(String),
+}
+
+impl PhotometricInterpretation {
+    /// Get the photometric interpretation from its defined term.
+    pub fn from_term(term: &str) -> Self {
+        match term {
+            "MONOCHROME1" => PhotometricInterpretation::Monochrome1,
+            "MONOCHROME2" => PhotometricInterpretation::Monochrome2,
+            "RGB" => PhotometricInterpretation::Rgb,
+            "PALETTE COLOR" => PhotometricInterpretation::PaletteColor,
+            "YBR_FULL" => PhotometricInterpretation::YbrFull,
+            "YBR_FULL_422" => PhotometricInterpretation::YbrFull422,
+            other => PhotometricInterpretation::Other(other.to_string()),
+        }
+    }
+}
+
+/// Dimensions and pixel depth of the image, as found in the group x0028.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ImageGeometry {
+    pub rows: u16,
+    pub columns: u16,
+    pub bits_allocated: u16,
+    pub bits_stored: u16,
+    pub samples_per_pixel: u16,
+}
+
+impl ImageGeometry {
+    /// Number of pixels in one frame.
+    pub fn pixel_count(&self) -> usize {
+        self.rows as usize * self.columns as usize
+    }
+
+    /// Number of samples in one frame (3 per pixel for RGB).
+    pub fn sample_count(&self) -> usize {
+        self.pixel_count() * self.samples_per_pixel as usize
+    }
+
+    #[cfg(feature = "image")]
+    fn check_buffer_len(&self, len: usize) -> DicomResult<()> {
+        if len != self.sample_count() {
+            Err(DicomError::ImageBufferSize(self.sample_count(), len))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that the geometry has `bits_allocated` bits per sample and that the bits stored fit
+    /// in them.
+    #[cfg(feature = "image")]
+    pub(crate) fn check_bits(&self, bits_allocated: u16) -> DicomResult<()> {
+        if self.bits_allocated != bits_allocated {
+            Err(DicomError::BitsAllocatedNotSupported(self.bits_allocated))
+        } else if self.bits_stored == 0 || self.bits_stored > self.bits_allocated {
+            Err(DicomError::InvalidBitsStored(self.bits_stored, self.bits_allocated))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Physical size of the pixels, in mm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelGeometry {
+    /// Distance between the centers of adjacent rows, then of adjacent columns.
+    pub pixel_spacing: [f64; 2],
+    pub slice_thickness: Option<f64>,
+}
+
+/// A window proposed by the object, to display the values between `center - width / 2` and
+/// `center + width / 2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowPreset {
+    pub center: f64,
+    pub width: f64,
+    pub explanation: Option<String>,
+}
 
 // for some reason image does not export this type...
-pub(crate) type Gray16Image = ImageBuffer<Luma<u16>, Vec<u16>>;
+#[cfg(feature = "image")]
+pub type Gray16Image = ImageBuffer<Luma<u16>, Vec<u16>>;
+#[cfg(feature = "image")]
+pub(crate) type GrayF32Image = ImageBuffer<Luma<f32>, Vec<f32>>;
+#[cfg(feature = "image")]
 pub enum DicomImage {
     Grayscale16 {
         image: Gray16Image,
@@ -21,35 +131,173 @@ pub enum DicomImage {
     Grayscale8 {
         image: image::GrayImage,
     },
+    Rgb8 {
+        image: image::RgbImage,
+    },
+    /// Values after a transformation such as the rescale, which can be negative (Hounsfield
+    /// units...).
+    GrayscaleF32 {
+        image: GrayF32Image,
+    },
+    /// Frames of a native or RLE multi-frame image (Number of Frames (x0028x0008) greater than 1).
+    MultiFrameGrayscale16 {
+        frames: Vec<Gray16Image>,
+    },
+    MultiFrameGrayscale8 {
+        frames: Vec<image::GrayImage>,
+    },
     Jpeg2000 {
         image: Vec<u8>,
     }
 }
 
+#[cfg(feature = "image")]
 impl fmt::Debug for DicomImage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             DicomImage::Grayscale16 { .. } => write!(f, "DicomImage::Grayscale16"),
             DicomImage::Grayscale8 { .. } => write!(f, "DicomImage::Grayscale8"),
+            DicomImage::Rgb8 { .. } => write!(f, "DicomImage::Rgb8"),
+            DicomImage::GrayscaleF32 { .. } => write!(f, "DicomImage::GrayscaleF32"),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => {
+                write!(f, "DicomImage::MultiFrameGrayscale16 ({} frames)", frames.len())
+            }
+            DicomImage::MultiFrameGrayscale8 { ref frames } => {
+                write!(f, "DicomImage::MultiFrameGrayscale8 ({} frames)", frames.len())
+            }
             DicomImage::Jpeg2000 { .. } => write!(f, "DicomImage::Jpeg2000"),
         }
     }
 }
 
+#[cfg(feature = "image")]
 impl DicomImage {
+    /// Decode 8 bits native pixel data into a buffer provided by the caller. This is useful to
+    /// reuse the same buffer when decoding a lot of images with the same size.
+    pub fn decode_u8_into(pixel_data: &[u8], geometry: &ImageGeometry, out: &mut [u8]) -> DicomResult<()> {
+        geometry.check_buffer_len(out.len())?;
+        geometry.check_bits(8)?;
+        parse_img_u8_into(pixel_data, out)?;
+        Ok(())
+    }
+
+    /// Same as `decode_u8_into` but for 16 bits images. Bits stored must be between 1 and 16.
+    pub fn decode_u16_into(
+        pixel_data: &[u8],
+        transfer_syntax: TransferSyntax,
+        geometry: &ImageGeometry,
+        out: &mut [u16],
+    ) -> DicomResult<()> {
+        geometry.check_buffer_len(out.len())?;
+        parse_img_u16_into(pixel_data, transfer_syntax.endianness(), geometry, out)?;
+        Ok(())
+    }
+
+    /// Save the image, the format comes from the extension of the path. Multi-frame images are
+    /// saved as their first frame. JPEG2000 images are saved as they were parsed.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> DicomResult<()> {
         match *self {
             DicomImage::Grayscale16 { ref image  } => image.save(path).map_err(|e| e.into()),
             DicomImage::Grayscale8 { ref image } => image.save(path).map_err(|e| e.into()),
+            DicomImage::Rgb8 { ref image } => image.save(path).map_err(|e| e.into()),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => {
+                first_frame(frames)?.save(path).map_err(|e| e.into())
+            }
+            DicomImage::MultiFrameGrayscale8 { ref frames } => {
+                first_frame(frames)?.save(path).map_err(|e| e.into())
+            }
+            DicomImage::GrayscaleF32 { .. } => Err(DicomError::ImageFormatNotSupported),
             DicomImage::Jpeg2000 { ref image } => {
                 let mut file = File::create(path)?;
-                file.write_all(&image).map_err(|e| e.into())
+                file.write_all(image).map_err(|e| e.into())
             },
         }
     }
 
-    pub fn thumbnail(&self, width: u32, height: u32) -> DicomImage {
+    /// Save a 16 bits grayscale image as a 16 bits PNG, whatever the extension of the path. The
+    /// image is encoded directly with `png` as the encoder of `image` does not always write the
+    /// samples in big endian. Multi-frame images are saved as their first frame. Other images
+    /// return `DicomError::ImageFormatNotSupported`.
+    pub fn save_png16<P: AsRef<Path>>(&self, path: P) -> DicomResult<()> {
+        let image = match *self {
+            DicomImage::Grayscale16 { ref image } => image,
+            DicomImage::MultiFrameGrayscale16 { ref frames } => first_frame(frames)?,
+            _ => return Err(DicomError::ImageFormatNotSupported),
+        };
+
+        let (width, height) = image.dimensions();
+        let data: Vec<u8> = image.iter().flat_map(|v| v.to_be_bytes().to_vec()).collect();
+        let mut encoder = png::Encoder::new(File::create(path)?, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Sixteen);
+        let mut writer = encoder.write_header().map_err(io::Error::from)?;
+        writer.write_image_data(&data).map_err(io::Error::from)?;
+        Ok(())
+    }
+
+    /// Save a 16 bits grayscale image as a 16 bits TIFF, whatever the extension of the path. See
+    /// `encode_tiff16`.
+    pub fn save_tiff16<P: AsRef<Path>>(&self, path: P, pixel_geometry: Option<PixelGeometry>) -> DicomResult<()> {
+        let data = self.encode_tiff16(pixel_geometry)?;
+        File::create(path)?.write_all(&data)?;
+        Ok(())
+    }
+
+    /// Encode a 16 bits grayscale image as a 16 bits TIFF, with one page per frame. When the pixel
+    /// geometry is known, the pixel spacing is written as the resolution, in pixels per
+    /// centimeter. Other images return `DicomError::ImageFormatNotSupported`.
+    pub fn encode_tiff16(&self, pixel_geometry: Option<PixelGeometry>) -> DicomResult<Vec<u8>> {
+        let frames = match *self {
+            DicomImage::Grayscale16 { ref image } => std::slice::from_ref(image),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => &frames[..],
+            _ => return Err(DicomError::ImageFormatNotSupported),
+        };
+
+        let mut out = io::Cursor::new(vec![]);
+        let mut encoder = tiff::encoder::TiffEncoder::new(&mut out)?;
+        for image in frames {
+            let (width, height) = image.dimensions();
+            let mut tiff = encoder.new_image::<tiff::encoder::colortype::Gray16>(width, height)?;
+            if let Some(PixelGeometry { pixel_spacing: [row_spacing, column_spacing], .. }) = pixel_geometry {
+                // Spacing is in mm, so pixels per cm = 10 / spacing = 10000 / (1000 * spacing).
+                let per_cm = |spacing: f64| tiff::encoder::Rational {
+                    n: 10_000,
+                    d: ((spacing * 1000.0).round() as u32).max(1),
+                };
+                tiff.resolution_unit(tiff::tags::ResolutionUnit::Centimeter);
+                tiff.x_resolution(per_cm(column_spacing));
+                tiff.y_resolution(per_cm(row_spacing));
+            }
+            tiff.write_data(image.as_raw())?;
+        }
+
+        Ok(out.into_inner())
+    }
+
+    /// Convert the image to 8 bits grayscale. 16 bits images are already scaled to the full
+    /// range during parsing so only the most significant byte is kept. Multi-frame images are
+    /// converted from their first frame.
+    pub fn to_gray8(&self) -> DicomResult<image::GrayImage> {
+        let gray16_to_gray8 = |image: &Gray16Image| {
+            let (width, height) = image.dimensions();
+            ImageBuffer::from_fn(width, height, |x, y| Luma([(image.get_pixel(x, y)[0] >> 8) as u8]))
+        };
         match *self {
+            DicomImage::Grayscale8 { ref image } => Ok(image.clone()),
+            DicomImage::Grayscale16 { ref image } => Ok(gray16_to_gray8(image)),
+            DicomImage::Rgb8 { ref image } => Ok(image::imageops::grayscale(image)),
+            DicomImage::MultiFrameGrayscale8 { ref frames } => Ok(first_frame(frames)?.clone()),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => Ok(gray16_to_gray8(first_frame(frames)?)),
+            DicomImage::GrayscaleF32 { .. } | DicomImage::Jpeg2000 { .. } => {
+                Err(DicomError::ImageFormatNotSupported)
+            }
+        }
+    }
+
+    /// Resize the image to fit in `width`x`height`, keeping the aspect ratio. Each frame of a
+    /// multi-frame image is resized. JPEG2000 images are not decoded so they cannot be resized.
+    pub fn thumbnail(&self, width: u32, height: u32) -> DicomResult<DicomImage> {
+        let image = match *self {
             DicomImage::Grayscale16 {
                 ref image,
             } => DicomImage::Grayscale16 {
@@ -60,15 +308,939 @@ impl DicomImage {
             } => DicomImage::Grayscale8 {
                 image: image::imageops::thumbnail(image, width, height),
             },
-            _ => unimplemented!()
+            DicomImage::Rgb8 {
+                ref image,
+            } => DicomImage::Rgb8 {
+                image: image::imageops::thumbnail(image, width, height),
+            },
+            DicomImage::GrayscaleF32 {
+                ref image,
+            } => DicomImage::GrayscaleF32 {
+                image: image::imageops::resize(image, width, height, image::imageops::FilterType::Triangle),
+            },
+            DicomImage::MultiFrameGrayscale16 { ref frames } => DicomImage::MultiFrameGrayscale16 {
+                frames: frames
+                    .iter()
+                    .map(|frame| image::imageops::thumbnail(frame, width, height))
+                    .collect(),
+            },
+            DicomImage::MultiFrameGrayscale8 { ref frames } => DicomImage::MultiFrameGrayscale8 {
+                frames: frames
+                    .iter()
+                    .map(|frame| image::imageops::thumbnail(frame, width, height))
+                    .collect(),
+            },
+            DicomImage::Jpeg2000 { .. } => return Err(DicomError::ImageFormatNotSupported),
+        };
+        Ok(image)
+    }
+
+    /// Extract the region of `width`x`height` pixels whose top left corner is at (`x`, `y`).
+    /// The region should be inside the image, otherwise `DicomError::RegionOutOfBounds` is
+    /// returned. JPEG2000 images are not decoded so they cannot be cropped.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> DicomResult<DicomImage> {
+        if let DicomImage::Jpeg2000 { .. } = *self {
+            return Err(DicomError::ImageFormatNotSupported);
         }
+        let (image_width, image_height) = self.dimensions();
+        let inside = |start: u32, len: u32, max: u32| start.checked_add(len).is_some_and(|end| end <= max);
+        if !inside(x, width, image_width) || !inside(y, height, image_height) {
+            return Err(DicomError::RegionOutOfBounds((x, y, width, height), image_width, image_height));
+        }
+
+        let image = match *self {
+            DicomImage::Grayscale16 { ref image } => DicomImage::Grayscale16 {
+                image: image::imageops::crop_imm(image, x, y, width, height).to_image(),
+            },
+            DicomImage::Grayscale8 { ref image } => DicomImage::Grayscale8 {
+                image: image::imageops::crop_imm(image, x, y, width, height).to_image(),
+            },
+            DicomImage::Rgb8 { ref image } => DicomImage::Rgb8 {
+                image: image::imageops::crop_imm(image, x, y, width, height).to_image(),
+            },
+            DicomImage::GrayscaleF32 { ref image } => DicomImage::GrayscaleF32 {
+                image: image::imageops::crop_imm(image, x, y, width, height).to_image(),
+            },
+            DicomImage::MultiFrameGrayscale16 { ref frames } => DicomImage::MultiFrameGrayscale16 {
+                frames: frames
+                    .iter()
+                    .map(|frame| image::imageops::crop_imm(frame, x, y, width, height).to_image())
+                    .collect(),
+            },
+            DicomImage::MultiFrameGrayscale8 { ref frames } => DicomImage::MultiFrameGrayscale8 {
+                frames: frames
+                    .iter()
+                    .map(|frame| image::imageops::crop_imm(frame, x, y, width, height).to_image())
+                    .collect(),
+            },
+            DicomImage::Jpeg2000 { .. } => unreachable!(),
+        };
+        Ok(image)
     }
 
     pub fn dimensions(&self) -> (u32, u32) {
         match *self {
             DicomImage::Grayscale16 { image: ref img} => img.dimensions(),
             DicomImage::Grayscale8 { image: ref img } => img.dimensions(),
-            _ => unimplemented!()
+            DicomImage::Rgb8 { image: ref img } => img.dimensions(),
+            DicomImage::GrayscaleF32 { image: ref img } => img.dimensions(),
+            // All the frames have the same dimensions.
+            DicomImage::MultiFrameGrayscale16 { ref frames } => {
+                frames.first().map_or((0, 0), |f| f.dimensions())
+            }
+            DicomImage::MultiFrameGrayscale8 { ref frames } => {
+                frames.first().map_or((0, 0), |f| f.dimensions())
+            }
+            DicomImage::Jpeg2000 { ref image } => jpeg2000_dimensions(image).unwrap_or((0, 0)),
+        }
+    }
+
+    /// Apply the rescale (value * slope + intercept) to each pixel of a grayscale image, for
+    /// example with the Rescale Slope (x0028x1053) and Intercept (x0028x1052) of a CT to get
+    /// Hounsfield units. The result can be negative so it is a `GrayscaleF32` image. Multi-frame
+    /// images are rescaled from their first frame.
+    ///
+    /// The pixels of the image are used as stored values, which is only true for 16 bits images
+    /// when all the bits are stored since they are scaled to the full range during parsing, and
+    /// for MONOCHROME2 since MONOCHROME1 is inverted. `DicomObject::apply_rescale` works with the
+    /// stored values in all cases.
+    pub fn apply_rescale(&self, slope: f64, intercept: f64) -> DicomResult<DicomImage> {
+        let rescale = |v: f64| (v * slope + intercept) as f32;
+        let image = match *self {
+            DicomImage::Grayscale16 { ref image } => map_gray(image, rescale),
+            DicomImage::Grayscale8 { ref image } => map_gray(image, rescale),
+            DicomImage::GrayscaleF32 { ref image } => map_gray(image, rescale),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => map_gray(first_frame(frames)?, rescale),
+            DicomImage::MultiFrameGrayscale8 { ref frames } => map_gray(first_frame(frames)?, rescale),
+            DicomImage::Rgb8 { .. } | DicomImage::Jpeg2000 { .. } => {
+                return Err(DicomError::ImageFormatNotSupported)
+            }
+        };
+        Ok(DicomImage::GrayscaleF32 { image })
+    }
+
+    /// Apply the linear VOI LUT function of the standard (C.11.2.1.2) to a grayscale image to get
+    /// an 8 bits image for display. The values below the window are black and the ones above are
+    /// white. A width of 1 (the minimum) is a threshold at the center. Multi-frame images are
+    /// windowed from their first frame and RGB images from their luma.
+    ///
+    /// The window is applied to the pixels of the image as they are, so a CT should be rescaled
+    /// with `apply_rescale` first when the window is in Hounsfield units. `DicomObject::window`
+    /// works with the modality values instead. JPEG2000 images are not decoded so they give an
+    /// empty image.
+    pub fn window(&self, center: f64, width: f64) -> image::GrayImage {
+        let voi = linear_voi(center, width);
+        match *self {
+            DicomImage::Grayscale16 { ref image } => map_gray(image, voi),
+            DicomImage::Grayscale8 { ref image } => map_gray(image, voi),
+            DicomImage::GrayscaleF32 { ref image } => map_gray(image, voi),
+            DicomImage::Rgb8 { ref image } => map_gray(&image::imageops::grayscale(image), voi),
+            DicomImage::MultiFrameGrayscale16 { ref frames } => {
+                frames.first().map_or_else(|| ImageBuffer::new(0, 0), |frame| map_gray(frame, voi))
+            }
+            DicomImage::MultiFrameGrayscale8 { ref frames } => {
+                frames.first().map_or_else(|| ImageBuffer::new(0, 0), |frame| map_gray(frame, voi))
+            }
+            DicomImage::Jpeg2000 { .. } => ImageBuffer::new(0, 0),
+        }
+    }
+}
+
+/// First frame of a multi-frame image, `DicomError::ImageFormatNotSupported` when there is none.
+#[cfg(feature = "image")]
+fn first_frame<I>(frames: &[I]) -> DicomResult<&I> {
+    frames.first().ok_or(DicomError::ImageFormatNotSupported)
+}
+
+/// Map each value of a grayscale image with `f`.
+#[cfg(feature = "image")]
+fn map_gray<T, U>(image: &ImageBuffer<Luma<T>, Vec<T>>, f: impl Fn(f64) -> U) -> ImageBuffer<Luma<U>, Vec<U>>
+where
+    T: image::Primitive + Into<f64> + 'static,
+    U: image::Primitive + 'static,
+{
+    let (width, height) = image.dimensions();
+    ImageBuffer::from_fn(width, height, |x, y| Luma([f(image.get_pixel(x, y)[0].into())]))
+}
+
+/// Read the dimensions in the image and tile size marker (SIZ) of a JPEG2000 codestream, which
+/// directly follows the start of codestream marker (SOC).
+#[cfg(feature = "image")]
+fn jpeg2000_dimensions(codestream: &[u8]) -> Option<(u32, u32)> {
+    if codestream.get(..4)? != [0xFF, 0x4F, 0xFF, 0x51] {
+        return None;
+    }
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = codestream.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    // Lsiz and Rsiz, then the size of the reference grid and the offset of the image in it.
+    let (width, height) = (read_u32(8)?, read_u32(12)?);
+    let (x_offset, y_offset) = (read_u32(16)?, read_u32(20)?);
+    Some((width.checked_sub(x_offset)?, height.checked_sub(y_offset)?))
+}
+
+/// Map the values to 8 bits with `voi`, the padded pixels are black.
+#[cfg(feature = "image")]
+fn apply_voi(values: &[f64], padding: &[bool], voi: impl Fn(f64) -> u8) -> Vec<u8> {
+    values
+        .iter()
+        .zip(padding)
+        .map(|(&v, &padding)| if padding { 0 } else { voi(v) })
+        .collect()
+}
+
+/// Linear function from the minimum to the maximum of the values which are not padding, to 8 bits.
+#[cfg(feature = "image")]
+fn stretch(values: &[f64], padding: &[bool]) -> impl Fn(f64) -> u8 {
+    let (min, max) = values
+        .iter()
+        .zip(padding)
+        .filter(|(_, &padding)| !padding)
+        .fold((f64::MAX, f64::MIN), |(min, max), (&v, _)| (min.min(v), max.max(v)));
+    let range = (max - min).max(f64::EPSILON);
+    move |v: f64| ((v - min) * 255.0 / range).clamp(0.0, 255.0) as u8
+}
+
+/// Linear VOI LUT function of the standard (C.11.2.1.2), to 8 bits.
+#[cfg(feature = "image")]
+fn linear_voi(center: f64, width: f64) -> impl Fn(f64) -> u8 {
+    let width = width.max(1.0);
+    let lower = center - 0.5 - (width - 1.0) / 2.0;
+    let upper = center - 0.5 + (width - 1.0) / 2.0;
+    move |x: f64| {
+        if x <= lower {
+            0
+        } else if x > upper {
+            255
+        } else {
+            (((x - (center - 0.5)) / (width - 1.0) + 0.5) * 255.0).round().clamp(0.0, 255.0) as u8
+        }
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the Photometric Interpretation (x0028x0004).
+    pub fn photometric_interpretation(&self) -> Option<PhotometricInterpretation> {
+        self.try_get::<String>(Tag::x0028x0004)
+            .ok()
+            .map(|term| PhotometricInterpretation::from_term(trim_padding(&term)))
+    }
+
+    /// Return the windows of the Window Center (x0028x1050) and Window Width (x0028x1051), with
+    /// their explanation (x0028x1055). The values are read as decimals even when they are stored
+    /// as integers. Empty if the window is missing or cannot be read.
+    pub fn window_presets(&self) -> Vec<WindowPreset> {
+        let centers: Vec<f64> = self.try_get(Tag::x0028x1050).unwrap_or_default();
+        let widths: Vec<f64> = self.try_get(Tag::x0028x1051).unwrap_or_default();
+        let explanations: Vec<String> = self.try_get(Tag::x0028x1055).unwrap_or_default();
+
+        centers
+            .into_iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (center, width))| WindowPreset {
+                center,
+                width,
+                explanation: explanations.get(i).filter(|e| !e.is_empty()).cloned(),
+            })
+            .collect()
+    }
+
+    /// Replace RLE pixel data by native pixel data and switch the object to explicit VR little
+    /// endian, so that it can be written for tools without codecs. Native objects are unchanged.
+    ///
+    /// Only RLE is supported: JPEG2000 pixel data is kept as the raw codestream by the parser
+    /// (`DicomImage::Jpeg2000`) and there is no decoder, so it fails with
+    /// `DicomError::ImageFormatNotSupported`.
+    pub fn decompress_pixels(&mut self) -> DicomResult<()> {
+        let scheme = match self.transfer_syntax.compression_scheme {
+            Some(scheme) => scheme,
+            None => return Ok(()),
+        };
+        if scheme != CompressionScheme::Rle {
+            return Err(DicomError::ImageFormatNotSupported);
+        }
+
+        let geometry = self.image_geometry()?;
+        let pixel_data = self.pixel_data.as_ref().ok_or(DicomError::NoSuchTag(Tag::x7FE0x0010))?;
+        let native = decode_rle_native(pixel_data, &geometry, self.number_of_frames())?;
+
+        self.pixel_data = Some(Cow::Owned(native));
+        if let Some(index) = self.element_index(Tag::x0028x0006) {
+            self.elements[index].data = Value::Owned(vec![0; 2]);
+        }
+        self.transfer_syntax.compression_scheme = None;
+        self.set_transfer_syntax(TransferSyntax::little_endian_explicit())
+    }
+
+    /// Return the Pixel Padding Value (x0028x0120), signed when the Pixel Representation
+    /// (x0028x0103) is 1. Pixels with this value are outside of the region of interest.
+    pub fn pixel_padding_value(&self) -> Option<i32> {
+        let value: u16 = self.try_get(Tag::x0028x0120).ok()?;
+        if self.try_get::<u16>(Tag::x0028x0103).unwrap_or(0) == 1 {
+            Some(value as i16 as i32)
+        } else {
+            Some(value as i32)
+        }
+    }
+
+    /// Return the stored values of the first frame of a native grayscale image, masked to the
+    /// Bits Stored (x0028x0101) and sign extended when the Pixel Representation (x0028x0103) is 1.
+    pub fn stored_values(&self) -> DicomResult<Vec<i32>> {
+        let geometry = self.image_geometry()?;
+        if self.transfer_syntax.is_encapsulated() || geometry.samples_per_pixel != 1 {
+            return Err(DicomError::ImageFormatNotSupported);
+        }
+        let pixel_data = self.pixel_data.as_ref().ok_or(DicomError::NoSuchTag(Tag::x7FE0x0010))?;
+        let bytes_per_sample = match geometry.bits_allocated {
+            8 => 1,
+            16 => 2,
+            bits => return Err(DicomError::BitsAllocatedNotSupported(bits)),
+        };
+        let len = geometry.sample_count() * bytes_per_sample;
+        if pixel_data.len() < len {
+            return Err(DicomError::ImageBufferSize(len, pixel_data.len()));
+        }
+
+        let bits_stored = geometry.bits_stored.clamp(1, geometry.bits_allocated) as u32;
+        let mask = (1u32 << bits_stored) - 1;
+        let signed = self.try_get::<u16>(Tag::x0028x0103).unwrap_or(0) == 1;
+        let endianness = self.transfer_syntax.endianness();
+        let values = pixel_data[..len]
+            .chunks_exact(bytes_per_sample)
+            .map(|sample| {
+                let raw = match (sample, endianness) {
+                    (&[b], _) => b as u32,
+                    (&[a, b], Endianness::Little) => u16::from_le_bytes([a, b]) as u32,
+                    (&[a, b], Endianness::Big) => u16::from_be_bytes([a, b]) as u32,
+                    _ => unreachable!(),
+                } & mask;
+                if signed && raw >> (bits_stored - 1) == 1 {
+                    raw as i32 - (1 << bits_stored)
+                } else {
+                    raw as i32
+                }
+            })
+            .collect();
+        Ok(values)
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'buf> DicomObject<'buf> {
+    /// Return the frames of a 16 bits grayscale image. A single frame image has one frame.
+    pub fn frames(&self) -> Option<&[Gray16Image]> {
+        match self.image {
+            Some(DicomImage::Grayscale16 { ref image }) => Some(std::slice::from_ref(image)),
+            Some(DicomImage::MultiFrameGrayscale16 { ref frames }) => Some(frames),
+            _ => None,
+        }
+    }
+
+    /// Same as `frames` for 8 bits grayscale images.
+    pub fn frames8(&self) -> Option<&[image::GrayImage]> {
+        match self.image {
+            Some(DicomImage::Grayscale8 { ref image }) => Some(std::slice::from_ref(image)),
+            Some(DicomImage::MultiFrameGrayscale8 { ref frames }) => Some(frames),
+            _ => None,
+        }
+    }
+
+    /// Return the image as 8 bits grayscale, ready to be displayed. MONOCHROME1 images (x0028x0004)
+    /// are already inverted by the parser so that the minimum value is black. The image is
+    /// inverted when the Presentation LUT Shape (x2050x0020) is INVERSE. Multi-frame images are
+    /// displayed from their first frame, see `DicomImage::to_gray8`.
+    pub fn to_display8(&self) -> DicomResult<image::GrayImage> {
+        let image = self.image.as_ref().ok_or(DicomError::NoSuchTag(Tag::x7FE0x0010))?;
+        let mut display = image.to_gray8()?;
+
+        let is_inverse = self
+            .try_get::<String>(Tag::x2050x0020)
+            .is_ok_and(|shape| trim_padding(&shape) == "INVERSE");
+        if is_inverse {
+            image::imageops::invert(&mut display);
+        }
+
+        Ok(display)
+    }
+
+    /// Render the first frame of a native grayscale image to 8 bits for display, from the stored
+    /// values, with the transformations that are present in the object:
+    ///
+    /// - Modality LUT or rescale, see `modality_values`.
+    /// - VOI: the first window (see `window_presets`), otherwise the first VOI LUT of the VOI LUT
+    ///   Sequence (x0028x3010), otherwise the values are stretched between their minimum and
+    ///   maximum like in `auto_normalize`.
+    /// - Inversion when the Presentation LUT Shape (x2050x0020) is INVERSE or for MONOCHROME1.
+    ///
+    /// Padded pixels (see `pixel_padding_value`) are black.
+    pub fn render8(&self) -> DicomResult<DicomImage> {
+        let geometry = self.image_geometry()?;
+        let (values, padding) = self.padded_modality_values()?;
+
+        let mut pixels: Vec<u8> = if let Some(window) = self.window_presets().first() {
+            apply_voi(&values, &padding, linear_voi(window.center, window.width))
+        } else if let Some(lut) = self.voi_lut()? {
+            let max = ((1u32 << lut.bits.clamp(1, 16)) - 1) as f64;
+            let scale = |entry: u16| (entry as f64 * 255.0 / max).round().min(255.0) as u8;
+            apply_voi(&values, &padding, |v| scale(lut.apply(v.round() as i32)))
+        } else {
+            apply_voi(&values, &padding, stretch(&values, &padding))
+        };
+
+        self.invert_for_display(&mut pixels, &padding);
+
+        let len = pixels.len();
+        let image = ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, pixels)
+            .ok_or(DicomError::ImageBufferSize(geometry.pixel_count(), len))?;
+        Ok(DicomImage::Grayscale8 { image })
+    }
+
+    /// Invert the pixels which are not padding for MONOCHROME1 or when the Presentation LUT Shape
+    /// (x2050x0020) is INVERSE, so that the minimum value is black.
+    fn invert_for_display(&self, pixels: &mut [u8], padding: &[bool]) {
+        let is_monochrome1 =
+            self.photometric_interpretation() == Some(PhotometricInterpretation::Monochrome1);
+        let is_inverse = self
+            .try_get::<String>(Tag::x2050x0020)
+            .is_ok_and(|shape| trim_padding(&shape) == "INVERSE");
+        if is_monochrome1 || is_inverse {
+            for (pixel, _) in pixels.iter_mut().zip(padding).filter(|(_, &padding)| !padding) {
+                *pixel = 255 - *pixel;
+            }
+        }
+    }
+
+    /// Apply the linear VOI LUT function of the standard (C.11.2.1.2) to the modality values of the
+    /// first frame (see `modality_values`) to get an 8 bits image for display. The values below
+    /// the window are black and the ones above are white. A width of 1 (the minimum) is a
+    /// threshold at the center.
+    ///
+    /// The window is in the unit of the modality values, e.g. Hounsfield units for a CT with a
+    /// rescale. Like in `render8`, the result is inverted for MONOCHROME1 and when the
+    /// Presentation LUT Shape (x2050x0020) is INVERSE. Padded pixels (see `pixel_padding_value`)
+    /// are black. `DicomImage::window` windows the pixels of a parsed image instead.
+    pub fn window(&self, center: f64, width: f64) -> DicomResult<image::GrayImage> {
+        let geometry = self.image_geometry()?;
+        let (values, padding) = self.padded_modality_values()?;
+        let mut pixels = apply_voi(&values, &padding, linear_voi(center, width));
+        self.invert_for_display(&mut pixels, &padding);
+        let len = pixels.len();
+        ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, pixels)
+            .ok_or(DicomError::ImageBufferSize(geometry.pixel_count(), len))
+    }
+
+    /// Return the modality values of the first frame and whether each pixel is padding (see
+    /// `pixel_padding_value`). The padding is compared to the stored values.
+    fn padded_modality_values(&self) -> DicomResult<(Vec<f64>, Vec<bool>)> {
+        let stored = self.stored_values()?;
+        let padding = self.padding_mask(&stored);
+        Ok((self.apply_modality(stored)?, padding))
+    }
+
+    /// Whether each stored value is the Pixel Padding Value.
+    fn padding_mask(&self, stored: &[i32]) -> Vec<bool> {
+        let padding_value = self.pixel_padding_value();
+        stored.iter().map(|&v| Some(v) == padding_value).collect()
+    }
+
+    /// Apply the rescale (value * slope + intercept) to the stored values of the first frame (see
+    /// `stored_values`), for example with the Rescale Slope (x0028x1053) and Intercept
+    /// (x0028x1052) of a CT to get Hounsfield units. The result can be negative so it is a
+    /// `GrayscaleF32` image. `modality_values` applies the rescale of the object.
+    ///
+    /// Unlike `DicomImage::apply_rescale` on the parsed image, the stored values are not scaled
+    /// to 16 bits nor inverted for MONOCHROME1.
+    pub fn apply_rescale(&self, slope: f64, intercept: f64) -> DicomResult<DicomImage> {
+        let geometry = self.image_geometry()?;
+        let values: Vec<f32> = self
+            .stored_values()?
+            .into_iter()
+            .map(|v| (v as f64 * slope + intercept) as f32)
+            .collect();
+        let len = values.len();
+        let image = ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, values)
+            .ok_or(DicomError::ImageBufferSize(geometry.pixel_count(), len))?;
+        Ok(DicomImage::GrayscaleF32 { image })
+    }
+
+    /// Stretch the stored values between their minimum and maximum to 8 bits. Padded pixels
+    /// (see `pixel_padding_value`) are mapped to 0 and excluded from the minimum and maximum.
+    pub fn auto_normalize(&self) -> DicomResult<image::GrayImage> {
+        let geometry = self.image_geometry()?;
+        let stored = self.stored_values()?;
+        let padding = self.padding_mask(&stored);
+        let values: Vec<f64> = stored.into_iter().map(|v| v as f64).collect();
+        let pixels = apply_voi(&values, &padding, stretch(&values, &padding));
+
+        let len = pixels.len();
+        ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, pixels)
+            .ok_or(DicomError::ImageBufferSize(geometry.pixel_count(), len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{text_element, us_element};
+    use crate::types::DataElement;
+
+    #[cfg(feature = "image")]
+    fn object_with_image(photometric_interpretation: &'static str) -> DicomObject<'static> {
+        let mut obj = DicomObject::new(
+            vec![text_element(Tag::x0028x0004, photometric_interpretation)],
+            TransferSyntax::little_endian_explicit(),
+        );
+        obj.image = Some(DicomImage::Grayscale16 {
+            image: ImageBuffer::from_raw(2, 1, vec![0x0000, 0x8000]).unwrap(),
+        });
+        obj
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn rescale_to_hounsfield_units() {
+        // MONOCHROME1 and 12 bits stored: the parsed image is inverted and scaled, not the
+        // stored values.
+        let mut obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0028x0004, "MONOCHROME1 "),
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 16),
+                us_element(Tag::x0028x0101, 12),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        let stored: Vec<u8> = [1000u16, 0].iter().flat_map(|v| v.to_le_bytes()).collect();
+        obj.pixel_data = Some(stored.into());
+
+        let rescaled = obj.apply_rescale(1.0, -1024.0).unwrap();
+        if let DicomImage::GrayscaleF32 { ref image } = rescaled {
+            assert_eq!(vec![-24.0, -1024.0], image.clone().into_raw());
+        } else {
+            panic!("Expected a float image, got {:?}", rescaled);
+        }
+        assert_eq!((1, 1), rescaled.crop(1, 0, 1, 1).unwrap().dimensions());
+
+        obj.elements.push(us_element(Tag::x0028x0002, 3));
+        assert!(matches!(obj.apply_rescale(1.0, 0.0), Err(DicomError::ImageFormatNotSupported)));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn rescale_image() {
+        let stored = DicomImage::Grayscale16 {
+            image: ImageBuffer::from_raw(2, 1, vec![1000, 0]).unwrap(),
+        };
+        let rescaled = stored.apply_rescale(1.0, -1024.0).unwrap();
+        if let DicomImage::GrayscaleF32 { ref image } = rescaled {
+            assert_eq!(vec![-24.0, -1024.0], image.clone().into_raw());
+        } else {
+            panic!("Expected a float image, got {:?}", rescaled);
+        }
+        // Rescaling again applies to the rescaled values.
+        let doubled = rescaled.apply_rescale(2.0, 0.0).unwrap();
+        if let DicomImage::GrayscaleF32 { image } = doubled {
+            assert_eq!(vec![-48.0, -2048.0], image.into_raw());
+        } else {
+            panic!("Expected a float image, got {:?}", doubled);
+        }
+
+        let rgb = DicomImage::Rgb8 { image: ImageBuffer::new(1, 1) };
+        assert!(matches!(rgb.apply_rescale(1.0, 0.0), Err(DicomError::ImageFormatNotSupported)));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn window_ct() {
+        let mut obj = ct_object("MONOCHROME2 ");
+        assert_eq!(vec![0, 0, 128, 255], obj.window(40.0, 400.0).unwrap().into_raw());
+        obj.elements.push(us_element(Tag::x0028x0120, 3000));
+        assert_eq!(vec![0, 0, 128, 0], obj.window(40.0, 400.0).unwrap().into_raw());
+
+        // Same orientation as render8: the minimum is white for MONOCHROME1, except the padding.
+        let mut obj = ct_object("MONOCHROME1 ");
+        obj.elements.push(us_element(Tag::x0028x0120, 3000));
+        assert_eq!(vec![255, 255, 127, 0], obj.window(40.0, 400.0).unwrap().into_raw());
+
+        // Width of 1: threshold at the center.
+        let mut obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 3),
+                us_element(Tag::x0028x0100, 8),
+                us_element(Tag::x0028x0101, 8),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        obj.pixel_data = Some(vec![9, 10, 11].into());
+        assert_eq!(vec![0, 255, 255], obj.window(10.0, 1.0).unwrap().into_raw());
+        assert_eq!(vec![0, 255, 255], obj.window(10.0, 0.0).unwrap().into_raw());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn window_image() {
+        let stored = DicomImage::Grayscale16 {
+            image: ImageBuffer::from_raw(5, 1, vec![0, 864, 1064, 1264, 3000]).unwrap(),
+        };
+        let hounsfield = stored.apply_rescale(1.0, -1024.0).unwrap();
+        assert_eq!(vec![0, 0, 128, 255, 255], hounsfield.window(40.0, 400.0).into_raw());
+
+        // Width of 1: threshold at the center.
+        let image = DicomImage::Grayscale8 { image: ImageBuffer::from_raw(3, 1, vec![9, 10, 11]).unwrap() };
+        assert_eq!(vec![0, 255, 255], image.window(10.0, 1.0).into_raw());
+        assert_eq!(vec![0, 255, 255], image.window(10.0, 0.0).into_raw());
+
+        let cropped = image.crop(2, 0, 1, 1).unwrap();
+        assert_eq!(vec![255], cropped.window(10.0, 1.0).into_raw());
+        let jpeg2000 = DicomImage::Jpeg2000 { image: vec![] };
+        assert_eq!((0, 0), jpeg2000.window(10.0, 1.0).dimensions());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn crop_region() {
+        let image = DicomImage::Grayscale8 {
+            image: ImageBuffer::from_fn(4, 3, |x, y| Luma([(10 * y + x) as u8])),
+        };
+        let cropped = image.crop(1, 1, 3, 2).unwrap();
+        assert_eq!((3, 2), cropped.dimensions());
+        if let DicomImage::Grayscale8 { image } = cropped {
+            assert_eq!(11, image.get_pixel(0, 0)[0]);
+            assert_eq!(23, image.get_pixel(2, 1)[0]);
+        } else {
+            panic!("Expected a 8 bits image, got {:?}", cropped);
+        }
+
+        assert!(image.crop(0, 0, 4, 3).is_ok());
+        assert!(matches!(image.crop(2, 0, 3, 1), Err(DicomError::RegionOutOfBounds(..))));
+        assert!(matches!(image.crop(0, 1, 1, u32::MAX), Err(DicomError::RegionOutOfBounds(..))));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn display_monochrome1_is_not_inverted_twice() {
+        // The parser already inverted the image.
+        let monochrome2 = object_with_image("MONOCHROME2 ").to_display8().unwrap();
+        let monochrome1 = object_with_image("MONOCHROME1 ").to_display8().unwrap();
+        assert_eq!(vec![0, 128], monochrome2.into_raw());
+        assert_eq!(vec![0, 128], monochrome1.into_raw());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn save_16_bits_png() {
+        let image = DicomImage::Grayscale16 {
+            image: ImageBuffer::from_raw(2, 1, vec![0x0102, 0xFF00]).unwrap(),
+        };
+        let path = std::env::temp_dir().join(format!("dicom-png16-{}.png", std::process::id()));
+        image.save_png16(&path).unwrap();
+
+        let saved = image::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(image::ColorType::L16, saved.color());
+        assert_eq!(vec![0x0102, 0xFF00], saved.to_luma16().into_raw());
+
+        let image = DicomImage::Grayscale8 {
+            image: ImageBuffer::from_raw(1, 1, vec![0]).unwrap(),
+        };
+        assert!(matches!(
+            image.save_png16(&path),
+            Err(DicomError::ImageFormatNotSupported)
+        ));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn save_16_bits_tiff() {
+        let image = DicomImage::Grayscale16 {
+            image: ImageBuffer::from_raw(2, 1, vec![0x0102, 0xFF00]).unwrap(),
+        };
+        let path = std::env::temp_dir().join(format!("dicom-tiff16-{}.tiff", std::process::id()));
+        let geometry = PixelGeometry { pixel_spacing: [0.5, 0.25], slice_thickness: None };
+        image.save_tiff16(&path, Some(geometry)).unwrap();
+
+        let saved = image::open(&path).unwrap();
+        assert_eq!(image::ColorType::L16, saved.color());
+        assert_eq!(vec![0x0102, 0xFF00], saved.to_luma16().into_raw());
+
+        let mut decoder = tiff::decoder::Decoder::new(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            decoder.get_tag(tiff::tags::Tag::XResolution).unwrap(),
+            tiff::decoder::ifd::Value::Rational(10_000, 250)
+        ));
+        assert!(matches!(
+            decoder.get_tag(tiff::tags::Tag::YResolution).unwrap(),
+            tiff::decoder::ifd::Value::Rational(10_000, 500)
+        ));
+
+        let image = DicomImage::Grayscale8 {
+            image: ImageBuffer::from_raw(1, 1, vec![0]).unwrap(),
+        };
+        assert!(matches!(image.encode_tiff16(None), Err(DicomError::ImageFormatNotSupported)));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn multi_frame_images() {
+        let image = DicomImage::MultiFrameGrayscale16 {
+            frames: vec![
+                ImageBuffer::from_raw(2, 2, vec![0x0100, 0x0200, 0x0300, 0x0400]).unwrap(),
+                ImageBuffer::from_raw(2, 2, vec![0xFF00; 4]).unwrap(),
+            ],
+        };
+        assert_eq!((2, 2), image.dimensions());
+        assert_eq!(vec![1, 2, 3, 4], image.to_gray8().unwrap().into_raw());
+        match image.thumbnail(1, 1).unwrap() {
+            DicomImage::MultiFrameGrayscale16 { frames } => {
+                assert_eq!(2, frames.len());
+                assert!(frames.iter().all(|frame| frame.dimensions() == (1, 1)));
+            }
+            thumbnail => panic!("Expected a multi-frame image, got {:?}", thumbnail),
+        }
+
+        let tiff = image.encode_tiff16(None).unwrap();
+        let mut decoder = tiff::decoder::Decoder::new(io::Cursor::new(tiff)).unwrap();
+        assert!(decoder.more_images());
+        decoder.next_image().unwrap();
+        assert!(!decoder.more_images());
+
+        let empty = DicomImage::MultiFrameGrayscale8 { frames: vec![] };
+        assert!(matches!(empty.to_gray8(), Err(DicomError::ImageFormatNotSupported)));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn jpeg2000_image() {
+        // SOC then SIZ: Lsiz, Rsiz, 10x6 reference grid with the image at (2, 1).
+        let mut codestream = vec![0xFF, 0x4F, 0xFF, 0x51, 0x00, 0x29, 0x00, 0x00];
+        for value in [10u32, 6, 2, 1] {
+            codestream.extend_from_slice(&value.to_be_bytes());
+        }
+        let image = DicomImage::Jpeg2000 { image: codestream };
+        assert_eq!((8, 5), image.dimensions());
+        assert!(matches!(image.thumbnail(4, 4), Err(DicomError::ImageFormatNotSupported)));
+
+        let image = DicomImage::Jpeg2000 { image: vec![0xFF, 0x4F] };
+        assert_eq!((0, 0), image.dimensions());
+    }
+
+    #[test]
+    fn photometric_interpretation() {
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0028x0004, "MONOCHROME2 ")],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            Some(PhotometricInterpretation::Monochrome2),
+            obj.photometric_interpretation()
+        );
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0028x0004, "YBR_FULL_422")],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            Some(PhotometricInterpretation::YbrFull422),
+            obj.photometric_interpretation()
+        );
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_explicit());
+        assert_eq!(None, obj.photometric_interpretation());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn auto_normalize_excludes_padding() {
+        let mut obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0010, 2),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 16),
+                us_element(Tag::x0028x0101, 12),
+                us_element(Tag::x0028x0103, 1),
+                us_element(Tag::x0028x0120, -2000i16 as u16),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        let stored: Vec<u8> = [100i16, 200, 300, -2000]
+            .iter()
+            .flat_map(|v| ((*v as u16) & 0x0FFF).to_le_bytes())
+            .collect();
+        obj.pixel_data = Some(stored.into());
+
+        assert_eq!(Some(-2000), obj.pixel_padding_value());
+        assert_eq!(vec![100, 200, 300, -2000], obj.stored_values().unwrap());
+        assert_eq!(vec![0, 127, 255, 0], obj.auto_normalize().unwrap().into_raw());
+    }
+
+    #[cfg(feature = "image")]
+    fn ct_object(photometric_interpretation: &'static str) -> DicomObject<'static> {
+        let mut obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0028x0004, photometric_interpretation),
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 4),
+                us_element(Tag::x0028x0100, 16),
+                us_element(Tag::x0028x0101, 16),
+                text_element(Tag::x0028x1052, "-1024 "),
+                text_element(Tag::x0028x1053, "1 "),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        let stored: Vec<u8> =
+            [0u16, 864, 1064, 3000].iter().flat_map(|v| v.to_le_bytes()).collect();
+        obj.pixel_data = Some(stored.into());
+        obj
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn render_ct() {
+        let render = |obj: &DicomObject| match obj.render8().unwrap() {
+            DicomImage::Grayscale8 { image } => image.into_raw(),
+            image => panic!("Expected a 8 bits image, got {:?}", image),
+        };
+
+        // Without window, stretched between -1024 and 1976 HU.
+        let mut obj = ct_object("MONOCHROME2 ");
+        assert_eq!(vec![0, 73, 90, 255], render(&obj));
+        // The padding is excluded: stretched between -160 and 1976 HU.
+        obj.elements.push(us_element(Tag::x0028x0120, 0));
+        assert_eq!(vec![0, 0, 23, 255], render(&obj));
+        obj.elements.pop();
+
+        obj.elements.push(text_element(Tag::x0028x1050, "40"));
+        obj.elements.push(text_element(Tag::x0028x1051, "400 "));
+        assert_eq!(vec![0, 0, 128, 255], render(&obj));
+
+        let mut obj = ct_object("MONOCHROME1 ");
+        obj.elements.push(text_element(Tag::x0028x1050, "40"));
+        obj.elements.push(text_element(Tag::x0028x1051, "400 "));
+        assert_eq!(vec![255, 255, 127, 0], render(&obj));
+        obj.elements.push(us_element(Tag::x0028x0120, 0));
+        assert_eq!(vec![0, 255, 127, 0], render(&obj));
+        obj.elements.pop();
+        // Inverted once, not a second time for the Presentation LUT Shape.
+        obj.elements.push(text_element(Tag::x2050x0020, "INVERSE "));
+        assert_eq!(vec![255, 255, 127, 0], render(&obj));
+
+        let mut obj = ct_object("MONOCHROME2 ");
+        obj.elements.push(text_element(Tag::x0028x1050, "40"));
+        obj.elements.push(text_element(Tag::x0028x1051, "400 "));
+        obj.elements.push(text_element(Tag::x2050x0020, "INVERSE "));
+        assert_eq!(vec![255, 255, 127, 0], render(&obj));
+    }
+
+    #[test]
+    fn decompress_rle_pixels() {
+        // 16 bits, 2 pixels: 0x0102 and 0x0304. Most significant bytes first.
+        let mut frame = vec![0u8; 64];
+        frame[0] = 2;
+        frame[4] = 64;
+        frame[8] = 67;
+        frame.extend_from_slice(&[0x01, 0x01, 0x03]);
+        frame.extend_from_slice(&[0x01, 0x02, 0x04]);
+        frame.push(0x80); // no-op run, so that the item has an even length.
+        let mut pixel_data = vec![0xFE, 0xFF, 0x00, 0xE0, 0x00, 0x00, 0x00, 0x00];
+        pixel_data.extend_from_slice(&[0xFE, 0xFF, 0x00, 0xE0]);
+        pixel_data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        pixel_data.extend_from_slice(&frame);
+        pixel_data.extend_from_slice(&[0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00]);
+
+        let mut obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 16),
+                us_element(Tag::x0028x0101, 16),
+            ],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Rle),
+        );
+        obj.pixel_data = Some(pixel_data.into());
+        obj.decompress_pixels().unwrap();
+
+        assert_eq!(TransferSyntax::little_endian_explicit(), obj.transfer_syntax);
+        assert_eq!("1.2.840.10008.1.2.1", trim_padding(&obj.get::<String>(Tag::x0002x0010)));
+        assert_eq!(Some(&[0x02, 0x01, 0x04, 0x03][..]), obj.pixel_data.as_deref());
+        assert_eq!(vec![0x0102, 0x0304], obj.stored_values().unwrap());
+
+        // Already native, nothing to do.
+        obj.decompress_pixels().unwrap();
+        assert_eq!(Some(&[0x02, 0x01, 0x04, 0x03][..]), obj.pixel_data.as_deref());
+
+        let mut jpeg2000 = DicomObject::new(
+            vec![],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossless),
+        );
+        assert!(matches!(
+            jpeg2000.decompress_pixels(),
+            Err(DicomError::ImageFormatNotSupported)
+        ));
+    }
+
+    #[test]
+    fn integer_window_presets() {
+        let is_element = |tag, value: &'static str| DataElement {
+            tag,
+            vr: Some(crate::ValueRepresentation::IS),
+            length: value.len() as u32,
+            data: Value::Buf(value.as_bytes()),
+        };
+        let obj = DicomObject::new(
+            vec![
+                is_element(Tag::x0028x1050, "40\\-600"),
+                is_element(Tag::x0028x1051, "400 \\1500"),
+                text_element(Tag::x0028x1055, "SOFT TISSUE\\LUNG"),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            vec![
+                WindowPreset {
+                    center: 40.0,
+                    width: 400.0,
+                    explanation: Some("SOFT TISSUE".to_string()),
+                },
+                WindowPreset {
+                    center: -600.0,
+                    width: 1500.0,
+                    explanation: Some("LUNG".to_string()),
+                },
+            ],
+            obj.window_presets()
+        );
+
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0028x1050, "40.0"),
+                text_element(Tag::x0028x1051, "400.0 "),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            vec![WindowPreset { center: 40.0, width: 400.0, explanation: None }],
+            obj.window_presets()
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn display_inverse_presentation_lut() {
+        let mut identity = object_with_image("MONOCHROME2 ");
+        identity.elements.push(text_element(Tag::x2050x0020, "IDENTITY"));
+        let mut inverse = object_with_image("MONOCHROME2 ");
+        inverse.elements.push(text_element(Tag::x2050x0020, "INVERSE "));
+
+        let identity = identity.to_display8().unwrap();
+        let inverse = inverse.to_display8().unwrap();
+        assert_eq!(vec![0, 128], identity.clone().into_raw());
+        for (i, id) in inverse.pixels().zip(identity.pixels()) {
+            assert_eq!(255 - id[0], i[0]);
         }
     }
 }
@@ -1,16 +1,35 @@
 mod error;
+mod file;
 mod img;
 mod tag;
 pub mod types;
 mod vr;
+pub mod dicomdir;
+pub mod lut;
+#[cfg(feature = "image")]
+pub mod overlay;
 pub mod parser;
+pub mod registration;
+pub mod rt;
+pub mod sop;
+pub mod sr;
+
+#[cfg(test)]
+mod test_helpers;
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+mod test_files;
 
 /*
     Crate exports.
 */
-pub use img::DicomImage;
+pub use img::{ImageGeometry, PhotometricInterpretation, PixelGeometry, WindowPreset};
+#[cfg(feature = "image")]
+pub use img::{DicomImage, Gray16Image};
 pub use error::{DicomError, DicomResult};
+pub use file::{open, parse_dir, parse_dir_with};
+pub use parser::encode::Writer;
 pub use parser::obj::Parser;
 pub use tag::Tag;
 pub use vr::ValueRepresentation;
-pub use types::{TransferSyntax, DicomObject};
\ No newline at end of file
+pub use types::{TransferSyntax, DicomObject, OwnedDicomObject};
\ No newline at end of file
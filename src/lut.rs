@@ -0,0 +1,265 @@
+//! Lookup tables (LUT) that map the stored values to other values.
+//!
+//! A LUT is described by the LUT Descriptor (x0028x3002) which has three values: the number of
+//! entries (0 means 65536), the first stored value that is mapped and the number of bits of the
+//! entries. The entries are in the LUT Data (x0028x3006). Stored values below the first mapped
+//! value are mapped to the first entry, and the ones after the end of the table to the last entry.
+//!
+//! The Modality LUT Sequence (x0028x3000) replaces the Rescale Slope and Intercept, for example in
+//! some RT Images.
+use crate::parser::sq::Item;
+use crate::types::FromDicomValue;
+use crate::{DicomError, DicomObject, DicomResult, Tag, TransferSyntax, ValueRepresentation};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use nom::number::Endianness;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut {
+    /// First stored value that is mapped.
+    pub first_mapped: i32,
+    /// Number of bits of the entries.
+    pub bits: u16,
+    pub data: Vec<u16>,
+}
+
+impl Lut {
+    /// Read a LUT from an item of a LUT sequence, with the transfer syntax of the item. `signed` is
+    /// true when the first mapped value is signed, which depends on the Pixel Representation
+    /// (x0028x0103).
+    ///
+    /// The descriptor should be US or SS, but some objects use a DS, so it is also accepted.
+    pub fn from_item(item: &Item, signed: bool) -> DicomResult<Lut> {
+        let transfer_syntax = &item.transfer_syntax;
+        let descriptor = item
+            .get_element(Tag::x0028x3002)
+            .ok_or(DicomError::MissingTag(Tag::x0028x3002))?;
+        let raw = descriptor
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("LUT Descriptor".to_string()))?;
+        let is_text = match descriptor.vr {
+            Some(ValueRepresentation::DS) | Some(ValueRepresentation::IS) => true,
+            Some(_) => false,
+            None => is_decimal_string(raw),
+        };
+
+        let (entries, first_mapped, bits) = if is_text {
+            let values: Vec<f64> = FromDicomValue::from_element(descriptor, transfer_syntax)?;
+            if values.len() != 3 {
+                return Err(DicomError::ParseError(format!(
+                    "LUT Descriptor should have 3 values but got {}",
+                    values.len()
+                )));
+            }
+            (values[0] as u32, values[1] as i32, values[2] as u16)
+        } else {
+            if raw.len() != 6 {
+                return Err(DicomError::ParseError(format!(
+                    "LUT Descriptor should have 6 bytes but got {}",
+                    raw.len()
+                )));
+            }
+            let values: Vec<u16> = raw.chunks_exact(2).map(|c| read_u16(c, transfer_syntax)).collect();
+            let first_mapped = if signed {
+                values[1] as i16 as i32
+            } else {
+                values[1] as i32
+            };
+            (values[0] as u32, first_mapped, values[2])
+        };
+        let entries = if entries == 0 { 65536 } else { entries as usize };
+
+        let data = item
+            .get_element(Tag::x0028x3006)
+            .and_then(|el| el.data.as_bytes())
+            .ok_or(DicomError::MissingTag(Tag::x0028x3006))?;
+        let data = if bits <= 8 && data.len() < entries * 2 {
+            unpack_8_bits(data, entries, transfer_syntax)?
+        } else {
+            if data.len() < entries * 2 {
+                return Err(DicomError::ParseError(format!(
+                    "LUT Data should have {} entries but has {} bytes",
+                    entries,
+                    data.len()
+                )));
+            }
+            data[..entries * 2]
+                .chunks_exact(2)
+                .map(|c| read_u16(c, transfer_syntax))
+                .collect()
+        };
+
+        Ok(Lut { first_mapped, bits, data })
+    }
+
+    /// Map a stored value.
+    pub fn apply(&self, value: i32) -> u16 {
+        let index = (value as i64 - self.first_mapped as i64).clamp(0, self.data.len() as i64 - 1);
+        self.data[index as usize]
+    }
+}
+
+/// 8 bits entries can be packed two per 16 bits word, the first entry being in the least
+/// significant byte.
+fn unpack_8_bits(data: &[u8], entries: usize, transfer_syntax: &TransferSyntax) -> DicomResult<Vec<u16>> {
+    if data.len() < entries {
+        return Err(DicomError::ParseError(format!(
+            "LUT Data should have {} entries of 8 bits but has {} bytes",
+            entries,
+            data.len()
+        )));
+    }
+    let entries = match transfer_syntax.endianness() {
+        Endianness::Little => data[..entries].iter().map(|&v| v as u16).collect(),
+        Endianness::Big => data
+            .chunks(2)
+            .flat_map(|word| word.iter().rev())
+            .take(entries)
+            .map(|&v| v as u16)
+            .collect(),
+    };
+    Ok(entries)
+}
+
+/// In implicit VR, a descriptor is read as text when it only has the characters of decimal
+/// strings: the 6 bytes of a binary descriptor can also be 3 short numbers such as "0\\0\\8 ".
+fn is_decimal_string(data: &[u8]) -> bool {
+    !data.is_empty()
+        && data
+            .iter()
+            .all(|&b| b.is_ascii_digit() || matches!(b, b'\\' | b'+' | b'-' | b'.' | b' ' | b'e' | b'E'))
+}
+
+fn read_u16(data: &[u8], transfer_syntax: &TransferSyntax) -> u16 {
+    match transfer_syntax.endianness() {
+        Endianness::Little => LittleEndian::read_u16(data),
+        Endianness::Big => BigEndian::read_u16(data),
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the first LUT of the Modality LUT Sequence (x0028x3000), if any.
+    pub fn modality_lut(&self) -> DicomResult<Option<Lut>> {
+        self.first_lut(Tag::x0028x3000)
+    }
+
+    /// Return the first LUT of the VOI LUT Sequence (x0028x3010), if any.
+    pub fn voi_lut(&self) -> DicomResult<Option<Lut>> {
+        self.first_lut(Tag::x0028x3010)
+    }
+
+    fn first_lut(&self, sequence: Tag) -> DicomResult<Option<Lut>> {
+        let item = match self
+            .get_element(sequence)
+            .and_then(|el| el.items())
+            .and_then(|items| items.first())
+        {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        let signed = self.try_get::<u16>(Tag::x0028x0103).unwrap_or(0) == 1;
+        Lut::from_item(item, signed).map(Some)
+    }
+
+    /// Return the stored values of the first frame after the modality transformation: the
+    /// Modality LUT when there is one, otherwise the Rescale Slope (x0028x1053) and Intercept
+    /// (x0028x1052).
+    pub fn modality_values(&self) -> DicomResult<Vec<f64>> {
+        self.apply_modality(self.stored_values()?)
+    }
+
+    /// Apply the modality transformation of `modality_values` to stored values.
+    pub(crate) fn apply_modality(&self, values: Vec<i32>) -> DicomResult<Vec<f64>> {
+        if let Some(lut) = self.modality_lut()? {
+            return Ok(values.into_iter().map(|v| lut.apply(v) as f64).collect());
+        }
+
+        let slope = self.try_get(Tag::x0028x1053).unwrap_or(1.0);
+        let intercept = self.try_get(Tag::x0028x1052).unwrap_or(0.0);
+        Ok(values.into_iter().map(|v| v as f64 * slope + intercept).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{element, vr_element};
+    use crate::types::{DataElement, Value};
+
+    fn u16_bytes(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn rt_image(descriptor: DataElement<'static>) -> DicomObject<'static> {
+        let lut = Item {
+            elements: vec![
+                descriptor,
+                vr_element(Tag::x0028x3006, ValueRepresentation::US, u16_bytes(&[10, 20, 30, 40])),
+            ],
+            transfer_syntax: TransferSyntax::little_endian_explicit(),
+        };
+        let mut obj = DicomObject::new(
+            vec![
+                vr_element(Tag::x0028x0010, ValueRepresentation::US, u16_bytes(&[1])),
+                vr_element(Tag::x0028x0011, ValueRepresentation::US, u16_bytes(&[4])),
+                vr_element(Tag::x0028x0100, ValueRepresentation::US, u16_bytes(&[16])),
+                vr_element(Tag::x0028x0101, ValueRepresentation::US, u16_bytes(&[16])),
+                vr_element(Tag::x0028x1053, ValueRepresentation::DS, b"2 ".to_vec()),
+                DataElement {
+                    tag: Tag::x0028x3000,
+                    vr: Some(ValueRepresentation::SQ),
+                    length: u32::MAX,
+                    data: Value::Sequence(vec![lut]),
+                },
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        obj.pixel_data = Some(u16_bytes(&[99, 100, 102, 200]).into());
+        obj
+    }
+
+    #[test]
+    fn apply_rt_modality_lut() {
+        let obj = rt_image(vr_element(Tag::x0028x3002, ValueRepresentation::US, u16_bytes(&[4, 100, 16])));
+        let lut = obj.modality_lut().unwrap().unwrap();
+        assert_eq!(100, lut.first_mapped);
+        assert_eq!(16, lut.bits);
+        // The LUT has priority over the rescale slope.
+        assert_eq!(vec![10.0, 10.0, 30.0, 40.0], obj.modality_values().unwrap());
+    }
+
+    #[test]
+    fn apply_8_bits_modality_lut() {
+        let mut obj = rt_image(vr_element(Tag::x0028x3002, ValueRepresentation::US, u16_bytes(&[4, 100, 8])));
+        if let Value::Sequence(items) = &mut obj.elements[5].data {
+            items[0].elements[1] = vr_element(Tag::x0028x3006, ValueRepresentation::OW, vec![5, 6, 7, 8]);
+        }
+        let lut = obj.modality_lut().unwrap().unwrap();
+        assert_eq!(8, lut.bits);
+        assert_eq!(vec![5, 6, 7, 8], lut.data);
+        assert_eq!(vec![5.0, 5.0, 7.0, 8.0], obj.modality_values().unwrap());
+
+        // An entry per 16 bits word is still accepted.
+        let obj = rt_image(vr_element(Tag::x0028x3002, ValueRepresentation::US, u16_bytes(&[4, 100, 8])));
+        assert_eq!(vec![10, 20, 30, 40], obj.modality_lut().unwrap().unwrap().data);
+    }
+
+    #[test]
+    fn apply_ds_modality_lut() {
+        let obj = rt_image(vr_element(Tag::x0028x3002, ValueRepresentation::DS, b"4\\101\\16 ".to_vec()));
+        assert_eq!(vec![10.0, 10.0, 20.0, 40.0], obj.modality_values().unwrap());
+
+        // Without VR, a text descriptor can have the 6 bytes of a binary one.
+        let obj = rt_image(element(Tag::x0028x3002, b"4\\1\\16".to_vec()));
+        let lut = obj.modality_lut().unwrap().unwrap();
+        assert_eq!((1, 16), (lut.first_mapped, lut.bits));
+        let obj = rt_image(element(Tag::x0028x3002, u16_bytes(&[4, 100, 16])));
+        let lut = obj.modality_lut().unwrap().unwrap();
+        assert_eq!((100, 16), (lut.first_mapped, lut.bits));
+
+        let mut obj = obj;
+        obj.elements.retain(|el| el.tag != Tag::x0028x3000);
+        assert_eq!(None, obj.modality_lut().unwrap());
+        assert_eq!(vec![198.0, 200.0, 204.0, 400.0], obj.modality_values().unwrap());
+    }
+}
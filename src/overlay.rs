@@ -0,0 +1,149 @@
+//! Overlays are bitmaps drawn on top of the image (annotations, regions of interest...). They
+//! are stored in the repeating groups x6000 to x601E, one plane per group.
+//!
+//! The overlay data (60xx,3000) is a bit stream, one bit per pixel. The rows are not aligned on
+//! bytes so a row can start in the middle of a byte.
+use crate::{DicomError, DicomObject, DicomResult, Tag};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use image::{GrayImage, Luma};
+use nom::number::Endianness;
+
+/// First and last groups of the overlay planes.
+const FIRST_OVERLAY_GROUP: u16 = 0x6000;
+const LAST_OVERLAY_GROUP: u16 = 0x601E;
+
+/// An overlay plane.
+#[derive(Debug)]
+pub struct Overlay {
+    /// Group of the overlay, between x6000 and x601E.
+    pub group: u16,
+    /// Position of the top left pixel of the overlay in the image as (row, column). As in the
+    /// standard, the first pixel of the image is (1, 1).
+    pub origin: (i16, i16),
+    /// Pixels that are part of the overlay are set to 255, other pixels are 0.
+    pub image: GrayImage,
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return all the overlay planes of the object.
+    pub fn overlays(&self) -> DicomResult<Vec<Overlay>> {
+        let mut overlays = vec![];
+        for group in (FIRST_OVERLAY_GROUP..=LAST_OVERLAY_GROUP).step_by(2) {
+            let data = match self.get_element(Tag::from_values(group, 0x3000)) {
+                Some(el) => el
+                    .data
+                    .as_bytes()
+                    .ok_or_else(|| DicomError::ConvertTypeExpectBuf("Overlay".to_string()))?,
+                None => continue,
+            };
+
+            let rows: u16 = self.try_get(Tag::from_values(group, 0x0010))?;
+            let columns: u16 = self.try_get(Tag::from_values(group, 0x0011))?;
+            let origin = self.overlay_origin(group)?;
+            let image = unpack_bits(data, rows, columns)?;
+            overlays.push(Overlay { group, origin, image });
+        }
+
+        Ok(overlays)
+    }
+
+    /// Overlay origin is two signed shorts (row, column).
+    fn overlay_origin(&self, group: u16) -> DicomResult<(i16, i16)> {
+        let tag = Tag::from_values(group, 0x0050);
+        let data = self
+            .get_element(tag)
+            .ok_or(DicomError::MissingTag(tag))?
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("Overlay Origin".to_string()))?;
+        if data.len() < 4 {
+            return Err(DicomError::ParseError(format!(
+                "Overlay origin should be 4 bytes (got {})",
+                data.len()
+            )));
+        }
+
+        Ok(match self.transfer_syntax.endianness() {
+            Endianness::Little => (LittleEndian::read_i16(data), LittleEndian::read_i16(&data[2..])),
+            Endianness::Big => (BigEndian::read_i16(data), BigEndian::read_i16(&data[2..])),
+        })
+    }
+}
+
+/// Unpack a bit stream (least significant bit first) to an image. The stream is continuous so
+/// the rows do not start on a byte boundary.
+pub(crate) fn unpack_bits(data: &[u8], rows: u16, columns: u16) -> DicomResult<GrayImage> {
+    let (width, height) = (columns as u32, rows as u32);
+    let nb_bits = width as usize * height as usize;
+    if data.len() * 8 < nb_bits {
+        return Err(DicomError::ParseError(format!(
+            "Expected {} bits of data but got {}",
+            nb_bits,
+            data.len() * 8
+        )));
+    }
+
+    Ok(GrayImage::from_fn(width, height, |x, y| {
+        let index = (y * width + x) as usize;
+        let bit = (data[index / 8] >> (index % 8)) & 1;
+        Luma([bit * 255])
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::element;
+    use crate::types::DataElement;
+    use crate::TransferSyntax;
+
+    fn overlay_elements(group: u16, origin: (i16, i16), data: Vec<u8>) -> Vec<DataElement<'static>> {
+        let mut origin_bytes = origin.0.to_le_bytes().to_vec();
+        origin_bytes.extend_from_slice(&origin.1.to_le_bytes());
+        vec![
+            element(Tag::from_values(group, 0x0010), 2u16.to_le_bytes().to_vec()),
+            element(Tag::from_values(group, 0x0011), 4u16.to_le_bytes().to_vec()),
+            element(Tag::from_values(group, 0x0050), origin_bytes),
+            element(Tag::from_values(group, 0x3000), data),
+        ]
+    }
+
+    #[test]
+    fn read_two_overlays() {
+        let mut elements = overlay_elements(0x6000, (1, 1), vec![0b0000_0001]);
+        elements.extend(overlay_elements(0x6002, (10, -3), vec![0b1000_0000]));
+        let obj = DicomObject::new(elements, TransferSyntax::little_endian_explicit());
+
+        let overlays = obj.overlays().unwrap();
+        assert_eq!(2, overlays.len());
+
+        assert_eq!(0x6000, overlays[0].group);
+        assert_eq!((1, 1), overlays[0].origin);
+        assert_eq!((4, 2), overlays[0].image.dimensions());
+        assert_eq!(255, overlays[0].image.get_pixel(0, 0)[0]);
+        assert_eq!(0, overlays[0].image.get_pixel(3, 1)[0]);
+
+        assert_eq!(0x6002, overlays[1].group);
+        assert_eq!((10, -3), overlays[1].origin);
+        assert_eq!(0, overlays[1].image.get_pixel(0, 0)[0]);
+        assert_eq!(255, overlays[1].image.get_pixel(3, 1)[0]);
+    }
+
+    #[test]
+    fn unpack_rows_across_bytes() {
+        // 5x3 overlay, an X shape:
+        // 1 0 0 0 1
+        // 0 1 0 1 0
+        // 0 0 1 0 0
+        // Bits 0, 4, 6, 8 and 12 are set so the second row starts in the first byte and ends in
+        // the second one.
+        let image = unpack_bits(&[0b0101_0001, 0b0001_0001], 3, 5).unwrap();
+        let pixels: Vec<u8> = image.pixels().map(|p| p[0] / 255).collect();
+        assert_eq!(
+            vec![1, 0, 0, 0, 1, 0, 1, 0, 1, 0, 0, 0, 1, 0, 0],
+            pixels
+        );
+
+        assert!(unpack_bits(&[0b0101_0001], 3, 5).is_err());
+    }
+}
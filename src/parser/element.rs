@@ -1,13 +1,27 @@
 use super::{parse_data, parse_length, parse_tag, parse_vr, sq::parse_seq};
 use crate::types::{TransferSyntax, Value, DataElement};
+use crate::{DicomError, Tag, ValueRepresentation};
 use log::trace;
 use nom::combinator::cond;
 use nom::IResult;
 
+/// Default maximum number of nested sequences, see `parse_dataelement_with_depth`.
+pub(crate) const MAX_NESTING_DEPTH: usize = 64;
+
 pub(crate) fn parse_dataelement(
     buf: &[u8],
     transfer_syntax: TransferSyntax,
-) -> IResult<&[u8], DataElement> {
+) -> IResult<&[u8], DataElement<'_>, DicomError> {
+    parse_dataelement_with_depth(buf, transfer_syntax, MAX_NESTING_DEPTH)
+}
+
+/// Parse a data element which can contain at most `max_depth` levels of nested sequences. The
+/// sequences are parsed recursively so this protects the stack from deeply nested sequences.
+pub(crate) fn parse_dataelement_with_depth(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    max_depth: usize,
+) -> IResult<&[u8], DataElement<'_>, DicomError> {
     // If no transfer syntax, we expect group 2. For the group 2, the Little endian, explicit VR is used.
     let endian = transfer_syntax.endianness();
     let (buf, tag) = parse_tag(buf, endian)?;
@@ -17,7 +31,21 @@ pub(crate) fn parse_dataelement(
     let (buf, length) = parse_length(buf, &vr, endian)?;
     trace!("LENGTH = {:?}", length);
 
-    let (buf, data) = parse_element_data(buf, length, transfer_syntax)?;
+    if length == u32::MAX && !accepts_undefined_length(tag, &vr) {
+        return Err(nom::Err::Failure(DicomError::UnexpectedUndefinedLength(tag)));
+    }
+
+    // The items of a sequence which VR is unknown are always in implicit VR little endian.
+    let data_syntax = if vr == Some(ValueRepresentation::UN) {
+        TransferSyntax::little_endian_implicit()
+    } else {
+        transfer_syntax
+    };
+    let is_sequence = match vr {
+        Some(ref vr) => *vr == ValueRepresentation::SQ,
+        None => tag.vr() == Some(ValueRepresentation::SQ),
+    };
+    let (buf, data) = parse_element_data(buf, length, is_sequence, data_syntax, max_depth)?;
     trace!("DATA = {:?}", data);
 
     Ok((
@@ -31,9 +59,31 @@ pub(crate) fn parse_dataelement(
     ))
 }
 
-fn parse_element_data(buf: &[u8], length: u32, transfer_syntax: TransferSyntax) -> IResult<&[u8], Value> {
-    if length == std::u32::MAX {
-        let (buf, items) = parse_seq(buf, length, transfer_syntax)?;
+/// Only sequences can have an undefined length (the encapsulated pixel data is parsed separately).
+/// UN is accepted as it can be a sequence which VR is unknown. Without VR, the dictionary decides
+/// and a tag it does not know may be a sequence.
+fn accepts_undefined_length(tag: Tag, vr: &Option<ValueRepresentation>) -> bool {
+    let vr = match vr {
+        Some(vr) => Some(vr.clone()),
+        None => tag.vr(),
+    };
+    matches!(
+        vr,
+        None | Some(ValueRepresentation::SQ) | Some(ValueRepresentation::UN)
+    )
+}
+
+/// Sequences can have an undefined length or a defined length. Other values with an undefined
+/// length are sequences which VR is unknown.
+fn parse_element_data(
+    buf: &[u8],
+    length: u32,
+    is_sequence: bool,
+    transfer_syntax: TransferSyntax,
+    max_depth: usize,
+) -> IResult<&[u8], Value<'_>, DicomError> {
+    if is_sequence || length == u32::MAX {
+        let (buf, items) = parse_seq(buf, length, transfer_syntax, max_depth)?;
         Ok((buf, Value::Sequence(items)))
     } else {
         let (buf, data) = parse_data(buf, length)?;
@@ -45,7 +95,6 @@ fn parse_element_data(buf: &[u8], length: u32, transfer_syntax: TransferSyntax)
 mod tests {
 
     use super::*;
-    use crate::{ValueRepresentation, Tag};
 
     #[test]
     fn parse_lee_dataelement() {
@@ -68,7 +117,7 @@ mod tests {
         if let Value::Buf(data) = data_element.data {
             assert_eq!(std::str::from_utf8(data).unwrap(), "benoit");
         } else {
-            assert!(false);
+            panic!("Expected a buffer");
         }
         assert_eq!(ValueRepresentation::CS, *data_element.vr.as_ref().unwrap());
     }
@@ -91,7 +140,7 @@ mod tests {
         if let Value::Buf(data) = data_element.data {
             assert_eq!(std::str::from_utf8(data).unwrap(), "benoit");
         } else {
-            assert!(false);
+            panic!("Expected a buffer");
         }
         assert!(data_element.vr.is_none());
     }
@@ -116,8 +165,69 @@ mod tests {
         if let Value::Buf(data) = data_element.data {
             assert_eq!(std::str::from_utf8(data).unwrap(), "benoit");
         } else {
-            assert!(false);
+            panic!("Expected a buffer");
         }
         assert_eq!(ValueRepresentation::CS, *data_element.vr.as_ref().unwrap());
     }
+
+    #[test]
+    fn parse_long_text() {
+        let mut data = vec![
+            0x20, 0x00, 0x00, 0x40, // image comments
+            b'L', b'T',
+            0x0A, 0x00,
+        ];
+        data.extend_from_slice(b"C:\\temp\\a ");
+
+        let (rest, data_element) =
+            parse_dataelement(&data, TransferSyntax::little_endian_explicit()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(Tag::x0020x4000, data_element.tag);
+        assert_eq!(Some(ValueRepresentation::LT), data_element.vr);
+
+        let values: Vec<String> = crate::types::FromDicomValue::from_element(
+            &data_element,
+            &TransferSyntax::little_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(vec!["C:\\temp\\a".to_string()], values);
+    }
+
+    #[test]
+    fn reject_undefined_length() {
+        // Rows is US in the dictionary, so an implicit VR element with an undefined length
+        // cannot be a sequence.
+        let data = vec![
+            0x28, 0x00, 0x10, 0x00, // rows
+            0xFF, 0xFF, 0xFF, 0xFF, // undefined length
+            0x00, 0x02,
+        ];
+        let res = parse_dataelement(&data, TransferSyntax::little_endian_implicit());
+        match res {
+            Err(nom::Err::Failure(DicomError::UnexpectedUndefinedLength(Tag::x0028x0010))) => (),
+            res => panic!("Expected an undefined length error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn un_sequence_items_are_implicit() {
+        let data = vec![
+            0x09, 0x00, 0x10, 0x10, b'U', b'N', 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFE, 0xFF, 0x00, 0xE0, 0xFF, 0xFF, 0xFF, 0xFF, // item
+            0x09, 0x00, 0x11, 0x10, 0x04, 0x00, 0x00, 0x00, b'A', b'B', b'C', b'D',
+            0xFE, 0xFF, 0x0D, 0xE0, 0x00, 0x00, 0x00, 0x00, // item delimitation
+            0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00, // sequence delimitation
+        ];
+
+        let (rest, el) = parse_dataelement(&data, TransferSyntax::little_endian_explicit()).unwrap();
+        assert!(rest.is_empty());
+        let items = el.items().unwrap();
+        assert_eq!(1, items.len());
+        assert_eq!(TransferSyntax::little_endian_implicit(), items[0].transfer_syntax);
+        assert_eq!(Tag::UNKNOWN(0x0009, 0x1011), items[0].elements[0].tag);
+        assert_eq!(None, items[0].elements[0].vr);
+
+        let encoded = crate::parser::encode::encode_dataelement(&el, TransferSyntax::little_endian_explicit()).unwrap();
+        assert_eq!(data, encoded);
+    }
 }
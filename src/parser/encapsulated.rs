@@ -0,0 +1,127 @@
+//! Encapsulated pixel data, used by compressed transfer syntaxes. The pixel data element has an
+//! undefined length and its value is a sequence of items, always encoded in little endian:
+//!
+//! - The first item is the Basic Offset Table. It contains the offset of each frame, relative to
+//!   the first fragment. It can be empty.
+//! - Then one item per fragment of compressed data. A frame can be split in multiple fragments.
+//! - The sequence ends with a Sequence delimitation item (xFFFExE0DD).
+//!
+//! See http://dicom.nema.org/dicom/2013/output/chtml/part05/sect_A.4.html
+use crate::parser::{parse_length, parse_tag, parse_u32};
+use crate::{DicomError, Tag};
+use nom::bytes::streaming::take;
+use nom::combinator::peek;
+use nom::number::Endianness;
+use nom::IResult;
+use std::borrow::Cow;
+
+/// Content of the encapsulated pixel data.
+#[derive(Debug)]
+pub(crate) struct EncapsulatedPixelData<'buf> {
+    /// Offsets of the frames. Empty if the Basic Offset Table is empty.
+    pub offset_table: Vec<u32>,
+    pub fragments: Vec<&'buf [u8]>,
+}
+
+impl<'buf> EncapsulatedPixelData<'buf> {
+    /// Return the compressed data of a frame. Without offset table, there is one fragment per
+    /// frame. Otherwise, the fragments that start between the offset of the frame and the offset
+    /// of the next frame are concatenated.
+    pub fn frame(&self, index: usize) -> Option<Cow<'buf, [u8]>> {
+        if self.offset_table.is_empty() {
+            return self.fragments.get(index).map(|f| Cow::Borrowed(*f));
+        }
+
+        let start = *self.offset_table.get(index)? as usize;
+        let end = self.offset_table.get(index + 1).map_or(usize::MAX, |&o| o as usize);
+        let mut offset = 0;
+        let mut fragments = vec![];
+        for fragment in &self.fragments {
+            if offset >= start && offset < end {
+                fragments.push(*fragment);
+            }
+            // Offsets include the item tag and length.
+            offset += 8 + fragment.len();
+        }
+
+        match fragments.len() {
+            0 => None,
+            1 => Some(Cow::Borrowed(fragments[0])),
+            _ => Some(Cow::Owned(fragments.concat())),
+        }
+    }
+}
+
+/// Parse one item (tag, length and value).
+fn parse_fragment(buf: &[u8]) -> IResult<&[u8], &[u8], DicomError> {
+    let (buf, tag) = parse_tag(buf, Endianness::Little)?;
+    if tag != Tag::xFFFExE000 {
+        return Err(nom::Err::Failure(DicomError::ParseError(format!(
+            "Expected an item but got {:?}",
+            tag
+        ))));
+    }
+    let (buf, length) = parse_length(buf, &None, Endianness::Little)?;
+    take(length)(buf)
+}
+
+/// Parse the value of the pixel data element when it is encapsulated.
+pub(crate) fn parse_encapsulated(buf: &[u8]) -> IResult<&[u8], EncapsulatedPixelData<'_>, DicomError> {
+    let (mut current, mut table) = parse_fragment(buf)?;
+    let mut offset_table = vec![];
+    while !table.is_empty() {
+        let (rest, offset) = parse_u32(table, Endianness::Little)?;
+        offset_table.push(offset);
+        table = rest;
+    }
+
+    let mut fragments = vec![];
+    loop {
+        let (_, next_tag) = peek(|i| parse_tag(i, Endianness::Little))(current)?;
+        match next_tag {
+            Tag::xFFFExE000 => {
+                let (rest, fragment) = parse_fragment(current)?;
+                fragments.push(fragment);
+                current = rest;
+            }
+            Tag::xFFFExE0DD => {
+                // Sequence delimitation item has a length of 0.
+                let (rest, _) = take(8usize)(current)?;
+                current = rest;
+                break;
+            }
+            tag => {
+                return Err(nom::Err::Failure(DicomError::ParseError(format!(
+                    "Expected an item or a sequence delimitation but got {:?}",
+                    tag
+                ))))
+            }
+        }
+    }
+
+    Ok((current, EncapsulatedPixelData { offset_table, fragments }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fragments() {
+        let data = vec![
+            0xFE, 0xFF, 0x00, 0xE0, 0x08, 0x00, 0x00, 0x00, // Basic Offset Table with 2 frames.
+            0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x00, 0x00,
+            0xFE, 0xFF, 0x00, 0xE0, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02, // first fragment
+            0xFE, 0xFF, 0x00, 0xE0, 0x04, 0x00, 0x00, 0x00, 0x03, 0x04, 0x05, 0x06,
+            0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00, // Sequence delimitation
+        ];
+
+        let (rest, pixel_data) = parse_encapsulated(&data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(vec![0, 10], pixel_data.offset_table);
+        assert_eq!(vec![&[1u8, 2][..], &[3, 4, 5, 6][..]], pixel_data.fragments);
+        assert_eq!(&[1, 2], &*pixel_data.frame(0).unwrap());
+        assert_eq!(&[3, 4, 5, 6], &*pixel_data.frame(1).unwrap());
+        assert!(pixel_data.frame(2).is_none());
+    }
+}
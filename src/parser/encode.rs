@@ -0,0 +1,549 @@
+//! Encoding of the data elements, the inverse of the parsing. Sequences and items are always
+//! written with an undefined length so that their content does not need to be encoded twice.
+use crate::types::{DataElement, TransferSyntax, Value};
+use crate::{DicomError, DicomObject, DicomResult, Tag, ValueRepresentation};
+use nom::number::Endianness;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// Write DICOM objects as files. `DicomObject::write` uses the default options.
+///
+/// ```no_run
+/// use dicom::Writer;
+///
+/// let content = std::fs::read("rle.dcm").unwrap();
+/// let obj = dicom::Parser::default().parse_object(&content).unwrap();
+/// let mut file = std::fs::File::create("native.dcm").unwrap();
+/// Writer::default().decompress_pixels(true).write_object(&obj, &mut file).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct Writer {
+    decompress_pixels: bool,
+}
+
+impl Writer {
+    /// If true, RLE pixel data is decoded and written as native pixel data in explicit VR little
+    /// endian, see `DicomObject::decompress_pixels`. The object is not modified.
+    ///
+    /// Only RLE is supported. The crate has no JPEG2000 decoder, so JPEG2000 pixel data fails
+    /// with `DicomError::ImageFormatNotSupported` and nothing is written; write it without this
+    /// option to keep it encapsulated.
+    pub fn decompress_pixels(mut self, decompress_pixels: bool) -> Self {
+        self.decompress_pixels = decompress_pixels;
+        self
+    }
+
+    /// Write the object as a DICOM file, see `DicomObject::write`.
+    pub fn write_object<W: Write>(&self, obj: &DicomObject, w: &mut W) -> DicomResult<()> {
+        if self.decompress_pixels && obj.transfer_syntax.is_encapsulated() {
+            let mut native = DicomObject::new(obj.elements.clone(), obj.transfer_syntax);
+            native.pixel_data = obj.pixel_data.clone();
+            native.decompress_pixels()?;
+            return native.write(w);
+        }
+        obj.write(w)
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Write the object as a DICOM file: the preamble, `DICM`, the group 2 in explicit VR little
+    /// endian, the other elements with the transfer syntax of the object and the pixel data.
+    ///
+    /// The File Meta Information Group Length (x0002x0000) is computed and the Transfer Syntax
+    /// UID (x0002x0010) is added when it is missing. Encapsulated pixel data is written as it
+    /// was parsed, with an undefined length. Use `Writer` to decompress it instead.
+    pub fn write<W: Write>(&self, w: &mut W) -> DicomResult<()> {
+        let meta_syntax = TransferSyntax::little_endian_explicit();
+        let mut meta_elements: Vec<&DataElement> = self
+            .elements
+            .iter()
+            .filter(|el| el.tag.get_group() == 2 && el.tag != Tag::x0002x0000)
+            .collect();
+        let ts_element;
+        if self.get_element(Tag::x0002x0010).is_none() {
+            let uid = self
+                .transfer_syntax
+                .known()
+                .ok_or_else(|| DicomError::TransferSyntaxNotSupported(format!("{:?}", self.transfer_syntax)))?
+                .uid();
+            ts_element = DataElement {
+                tag: Tag::x0002x0010,
+                vr: Some(ValueRepresentation::UI),
+                length: uid.len() as u32,
+                data: Value::Buf(uid.as_bytes()),
+            };
+            meta_elements.push(&ts_element);
+            meta_elements.sort_by_key(|el| el.tag.get_element());
+        }
+        let mut meta = vec![];
+        for el in meta_elements {
+            encode_into(el, meta_syntax, &mut meta)?;
+        }
+
+        let group_length = DataElement {
+            tag: Tag::x0002x0000,
+            vr: Some(ValueRepresentation::UL),
+            length: 4,
+            data: Value::Owned((meta.len() as u32).to_le_bytes().to_vec()),
+        };
+        let mut out = vec![0; 128];
+        out.extend_from_slice(b"DICM");
+        encode_into(&group_length, meta_syntax, &mut out)?;
+        out.extend(meta);
+
+        for el in self.elements.iter().filter(|el| el.tag.get_group() != 2) {
+            encode_into(el, self.transfer_syntax, &mut out)?;
+        }
+
+        if let Some(ref pixel_data) = self.pixel_data {
+            if self.transfer_syntax.is_encapsulated() {
+                let endian = Endianness::Little;
+                encode_tag(Tag::x7FE0x0010, endian, &mut out);
+                out.extend_from_slice(b"OB");
+                out.extend_from_slice(&[0, 0]);
+                encode_u32(u32::MAX, endian, &mut out);
+                out.extend_from_slice(pixel_data);
+            } else {
+                let bits_allocated: u16 = self.try_get(Tag::x0028x0100).unwrap_or(8);
+                let vr = if bits_allocated > 8 {
+                    ValueRepresentation::OW
+                } else {
+                    ValueRepresentation::OB
+                };
+                let pixel_element = DataElement {
+                    tag: Tag::x7FE0x0010,
+                    vr: Some(vr),
+                    length: pixel_data.len() as u32,
+                    data: Value::Buf(pixel_data),
+                };
+                encode_into(&pixel_element, self.transfer_syntax, &mut out)?;
+            }
+        }
+
+        w.write_all(&out)?;
+        Ok(())
+    }
+}
+
+/// Encode a data element (tag, VR, length and value) with the given transfer syntax. In explicit
+/// VR, the VR comes from the element or from the dictionary and UN is used when it is unknown.
+///
+/// Fails when the value is too long for its length field, e.g. more than 65535 bytes for a VR
+/// which length has 2 bytes in explicit VR.
+pub fn encode_dataelement(el: &DataElement, transfer_syntax: TransferSyntax) -> DicomResult<Vec<u8>> {
+    let mut out = vec![];
+    encode_into(el, transfer_syntax, &mut out)?;
+    Ok(out)
+}
+
+fn encode_into(el: &DataElement, transfer_syntax: TransferSyntax, out: &mut Vec<u8>) -> DicomResult<()> {
+    let endian = transfer_syntax.endianness();
+    let vr = el
+        .vr
+        .clone()
+        .or_else(|| el.tag.vr())
+        .unwrap_or(ValueRepresentation::UN);
+
+    let value = match el.data {
+        Value::Buf(data) => padded(data, &vr),
+        Value::Owned(ref data) => padded(data, &vr),
+        Value::Sequence(_) => vec![],
+    };
+    let too_long = || DicomError::ValueTooLong(el.tag, vr.clone(), value.len());
+    let length = match el.data {
+        Value::Sequence(_) => u32::MAX,
+        _ => u32::try_from(value.len()).map_err(|_| too_long())?,
+    };
+
+    let header_len = if transfer_syntax.is_vr_explicit() {
+        vr.header_len()
+    } else {
+        ValueRepresentation::implicit_header_len()
+    };
+    out.reserve(header_len + value.len());
+
+    encode_tag(el.tag, endian, out);
+    if transfer_syntax.is_vr_explicit() {
+        out.extend_from_slice(vr.as_str().as_bytes());
+        if vr.has_special_length() {
+            out.extend_from_slice(&[0, 0]);
+            encode_u32(length, endian, out);
+        } else {
+            let length = u16::try_from(length).map_err(|_| too_long())?;
+            encode_u16(length, endian, out);
+        }
+    } else {
+        encode_u32(length, endian, out);
+    }
+
+    match el.data {
+        Value::Sequence(ref items) => {
+            let transfer_syntax = items_transfer_syntax(&vr, transfer_syntax);
+            let endian = transfer_syntax.endianness();
+            for item in items {
+                encode_tag(Tag::xFFFExE000, endian, out);
+                encode_u32(u32::MAX, endian, out);
+                for el in &item.elements {
+                    encode_into(el, transfer_syntax, out)?;
+                }
+                encode_tag(Tag::xFFFExE00D, endian, out);
+                encode_u32(0, endian, out);
+            }
+            encode_tag(Tag::xFFFExE0DD, endian, out);
+            encode_u32(0, endian, out);
+        }
+        _ => out.extend_from_slice(&value),
+    }
+    Ok(())
+}
+
+/// Transfer syntax of the items of a sequence written with `transfer_syntax`. The items of a UN
+/// sequence are always in implicit VR little endian.
+pub(crate) fn items_transfer_syntax(vr: &ValueRepresentation, transfer_syntax: TransferSyntax) -> TransferSyntax {
+    if transfer_syntax.is_vr_explicit() && *vr == ValueRepresentation::UN {
+        TransferSyntax::little_endian_implicit()
+    } else {
+        transfer_syntax
+    }
+}
+
+/// Values must have an even length. UI and binary values are padded with a null byte, text
+/// values with a space.
+fn padded(data: &[u8], vr: &ValueRepresentation) -> Vec<u8> {
+    let mut data = data.to_vec();
+    if data.len() % 2 == 1 {
+        let padding = match vr {
+            ValueRepresentation::UI
+            | ValueRepresentation::OB
+            | ValueRepresentation::UN
+            | ValueRepresentation::UNKNOWN(_) => 0,
+            _ => b' ',
+        };
+        data.push(padding);
+    }
+    data
+}
+
+fn encode_tag(tag: Tag, endian: Endianness, out: &mut Vec<u8>) {
+    encode_u16(tag.get_group(), endian, out);
+    encode_u16(tag.get_element(), endian, out);
+}
+
+fn encode_u16(value: u16, endian: Endianness, out: &mut Vec<u8>) {
+    match endian {
+        Endianness::Little => out.extend_from_slice(&value.to_le_bytes()),
+        Endianness::Big => out.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+fn encode_u32(value: u32, endian: Endianness, out: &mut Vec<u8>) {
+    match endian {
+        Endianness::Little => out.extend_from_slice(&value.to_le_bytes()),
+        Endianness::Big => out.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::element::parse_dataelement;
+    use crate::parser::sq::Item;
+    use crate::test_helpers::{sequence, text_element, us_element};
+    use crate::DicomObject;
+
+    #[test]
+    fn transcode_implicit_to_explicit() {
+        let code = sequence(
+            Tag::x0040xA043,
+            vec![vec![text_element(Tag::x0008x0100, "T-04000 ")]],
+        );
+        let mut obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0002x0010, "1.2.840.10008.1.2\0"),
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                us_element(Tag::x0028x0010, 512),
+                text_element(Tag::UNKNOWN(0x0009, 0x0010), "PRIVATE "),
+                code,
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        obj.set_transfer_syntax(TransferSyntax::little_endian_explicit())
+            .unwrap();
+        assert_eq!(TransferSyntax::little_endian_explicit(), obj.transfer_syntax);
+
+        let mut bytes = vec![];
+        for el in obj.elements() {
+            bytes.extend(encode_dataelement(el, obj.transfer_syntax).unwrap());
+        }
+
+        let mut parsed = vec![];
+        let mut current = bytes.as_slice();
+        while !current.is_empty() {
+            let (rest, el) = parse_dataelement(current, TransferSyntax::little_endian_explicit())
+                .unwrap();
+            parsed.push(el);
+            current = rest;
+        }
+        let vrs: Vec<_> = parsed.iter().map(|el| el.vr.clone().unwrap()).collect();
+        assert_eq!(
+            vec![
+                ValueRepresentation::UI,
+                ValueRepresentation::PN,
+                ValueRepresentation::US,
+                ValueRepresentation::UN,
+                ValueRepresentation::SQ
+            ],
+            vrs
+        );
+
+        let parsed = DicomObject::new(parsed, TransferSyntax::little_endian_explicit());
+        assert_eq!(
+            "1.2.840.10008.1.2.1\0",
+            parsed.get::<String>(Tag::x0002x0010)
+        );
+        assert_eq!(512, parsed.get::<u16>(Tag::x0028x0010));
+        let item = &parsed.get_element(Tag::x0040xA043).unwrap().items().unwrap()[0];
+        assert_eq!(
+            "T-04000 ",
+            item.try_get::<String>(Tag::x0008x0100).unwrap()
+        );
+    }
+
+    /// Parse the element then check that encoding it gives the same bytes.
+    fn assert_reencoded(data: &[u8], transfer_syntax: TransferSyntax) {
+        let (rest, el) = parse_dataelement(data, transfer_syntax).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(data, encode_dataelement(&el, transfer_syntax).unwrap().as_slice());
+    }
+
+    #[test]
+    fn reencode_dataelements() {
+        // The elements of the parser tests: little endian explicit and implicit, big endian.
+        let lee = [0x10, 0x00, 0x10, 0x00, b'C', b'S', 0x06, 0x00, b'b', b'e', b'n', b'o', b'i', b't'];
+        assert_reencoded(&lee, TransferSyntax::little_endian_explicit());
+        let lei = [0x10, 0x00, 0x10, 0x00, 0x06, 0x00, 0x00, 0x00, b'b', b'e', b'n', b'o', b'i', b't'];
+        assert_reencoded(&lei, TransferSyntax::little_endian_implicit());
+        let bee = [0x00, 0x10, 0x00, 0x10, b'C', b'S', 0x00, 0x06, b'b', b'e', b'n', b'o', b'i', b't'];
+        assert_reencoded(&bee, TransferSyntax::big_endian_explicit());
+
+        // Special length: 2 bytes of padding then the length on 4 bytes.
+        let ut = [0x08, 0x00, 0x30, 0x10, b'U', b'T', 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, b'A', b'B'];
+        assert_reencoded(&ut, TransferSyntax::little_endian_explicit());
+        let ob = [0x00, 0x09, 0x00, 0x10, b'O', b'B', 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x02];
+        assert_reencoded(&ob, TransferSyntax::big_endian_explicit());
+    }
+
+    #[test]
+    fn reencode_sequences() {
+        let explicit = vec![
+            0x40, 0x00, 0x43, 0xA0, b'S', b'Q', 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFE, 0xFF, 0x00, 0xE0, 0xFF, 0xFF, 0xFF, 0xFF, // item
+            0x08, 0x00, 0x00, 0x01, b'S', b'H', 0x04, 0x00, b'1', b'2', b'3', b'4',
+            0xFE, 0xFF, 0x0D, 0xE0, 0x00, 0x00, 0x00, 0x00, // item delimitation
+            0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00, // sequence delimitation
+        ];
+        assert_reencoded(&explicit, TransferSyntax::little_endian_explicit());
+
+        let implicit = vec![
+            0x40, 0x00, 0x43, 0xA0, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFE, 0xFF, 0x00, 0xE0, 0xFF, 0xFF, 0xFF, 0xFF, // item
+            0x08, 0x00, 0x00, 0x01, 0x04, 0x00, 0x00, 0x00, b'1', b'2', b'3', b'4',
+            0xFE, 0xFF, 0x0D, 0xE0, 0x00, 0x00, 0x00, 0x00, // item delimitation
+            0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00, // sequence delimitation
+        ];
+        assert_reencoded(&implicit, TransferSyntax::little_endian_implicit());
+
+        let big_endian = vec![
+            0x00, 0x40, 0xA0, 0x43, b'S', b'Q', 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFE, 0xE0, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, // item
+            0x00, 0x08, 0x01, 0x00, b'S', b'H', 0x00, 0x04, b'1', b'2', b'3', b'4',
+            0xFF, 0xFE, 0xE0, 0x0D, 0x00, 0x00, 0x00, 0x00, // item delimitation
+            0xFF, 0xFE, 0xE0, 0xDD, 0x00, 0x00, 0x00, 0x00, // sequence delimitation
+        ];
+        assert_reencoded(&big_endian, TransferSyntax::big_endian_explicit());
+    }
+
+    fn assert_round_trip(content: &[u8]) {
+        let obj = crate::Parser::default().parse_object(content).unwrap();
+        let mut written = vec![];
+        obj.write(&mut written).unwrap();
+        let parsed = crate::Parser::default().parse_object(&written).unwrap();
+
+        assert_eq!(obj.transfer_syntax, parsed.transfer_syntax);
+        let values = |obj: &DicomObject| -> Vec<(Tag, Option<Vec<u8>>)> {
+            obj.elements()
+                .iter()
+                .filter(|el| el.tag != Tag::x0002x0000)
+                .map(|el| (el.tag, el.data.as_bytes().map(|data| data.to_vec())))
+                .collect()
+        };
+        assert_eq!(values(&obj), values(&parsed));
+        assert_eq!(obj.pixel_data, parsed.pixel_data);
+
+        // The group length is the length of the rest of the group 2.
+        let meta = parsed.meta_bytes().unwrap();
+        assert_eq!((meta.len() - 144) as u32, parsed.get::<u32>(Tag::x0002x0000));
+    }
+
+    #[test]
+    fn write_explicit_file() {
+        assert_round_trip(&crate::test_files::fixture("DOE^JOHN", &[]));
+    }
+
+    #[test]
+    fn write_implicit_file() {
+        use crate::test_files::explicit_le;
+
+        let implicit = |group: u16, element: u16, value: &[u8]| {
+            let mut bytes = vec![];
+            bytes.extend_from_slice(&group.to_le_bytes());
+            bytes.extend_from_slice(&element.to_le_bytes());
+            bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(value);
+            bytes
+        };
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0000, "UL", &28u32.to_le_bytes()));
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2\0"));
+        content.extend(implicit(0x0010, 0x0010, b"DOE^JANE"));
+        content.extend(implicit(0x0028, 0x0010, &1u16.to_le_bytes()));
+        content.extend(implicit(0x0028, 0x0011, &2u16.to_le_bytes()));
+        content.extend(implicit(0x0028, 0x0100, &16u16.to_le_bytes()));
+        content.extend(implicit(0x0028, 0x0101, &12u16.to_le_bytes()));
+        content.extend(implicit(0x7FE0, 0x0010, &[0x01, 0x02, 0x03, 0x04]));
+        assert_round_trip(&content);
+    }
+
+    #[test]
+    fn reject_too_long_value() {
+        // The length of LT has 2 bytes in explicit VR but 4 bytes in implicit VR.
+        let text = vec![b'A'; 70000];
+        let el = DataElement {
+            tag: Tag::x0008x0100,
+            vr: Some(ValueRepresentation::LT),
+            length: text.len() as u32,
+            data: Value::Owned(text),
+        };
+        match encode_dataelement(&el, TransferSyntax::little_endian_explicit()) {
+            Err(DicomError::ValueTooLong(Tag::x0008x0100, ValueRepresentation::LT, 70000)) => (),
+            res => panic!("Expected a too long value error, got {:?}", res.map(|b| b.len())),
+        }
+        let encoded = encode_dataelement(&el, TransferSyntax::little_endian_implicit()).unwrap();
+        assert_eq!(70008, encoded.len());
+    }
+
+    #[test]
+    fn write_without_meta() {
+        let mut obj = DicomObject::new(
+            vec![text_element(Tag::x0010x0010, "DOE^JOHN")],
+            TransferSyntax::big_endian_explicit(),
+        );
+        obj.pixel_data = Some(vec![1, 2, 3].into());
+        let mut written = vec![];
+        obj.write(&mut written).unwrap();
+
+        let parsed = crate::Parser::new(false).parse_object(&written).unwrap();
+        assert_eq!(TransferSyntax::big_endian_explicit(), parsed.transfer_syntax);
+        assert_eq!(Tag::x0002x0000, parsed.elements()[0].tag);
+        assert_eq!(Tag::x0002x0010, parsed.elements()[1].tag);
+        // Odd values are padded.
+        assert_eq!(&[1, 2, 3, 0], &written[written.len() - 4..]);
+    }
+
+    #[test]
+    fn transcode_un_sequence() {
+        // The items of a UN sequence stay in implicit VR little endian whatever the transfer
+        // syntax of the object, so their values must not be swapped.
+        let un = DataElement {
+            tag: Tag::UNKNOWN(0x0009, 0x1010),
+            vr: Some(ValueRepresentation::UN),
+            length: u32::MAX,
+            data: Value::Sequence(vec![Item {
+                elements: vec![us_element(Tag::x0028x0010, 512)],
+                transfer_syntax: TransferSyntax::little_endian_implicit(),
+            }]),
+        };
+        let mut obj = DicomObject::new(
+            vec![text_element(Tag::x0010x0010, "DOE^JOHN"), un],
+            TransferSyntax::little_endian_explicit(),
+        );
+        obj.set_transfer_syntax(TransferSyntax::big_endian_explicit())
+            .unwrap();
+        let mut written = vec![];
+        obj.write(&mut written).unwrap();
+
+        let parsed = crate::Parser::new(false).parse_object(&written).unwrap();
+        assert_eq!(TransferSyntax::big_endian_explicit(), parsed.transfer_syntax);
+        let item = &parsed.get_element(Tag::UNKNOWN(0x0009, 0x1010)).unwrap().items().unwrap()[0];
+        assert_eq!(TransferSyntax::little_endian_implicit(), item.transfer_syntax);
+        assert_eq!(
+            512,
+            item.try_get::<u16>(Tag::x0028x0010).unwrap()
+        );
+    }
+
+    /// Encapsulated pixel data with a single fragment and an empty basic offset table.
+    fn encapsulated(fragment: &[u8]) -> Vec<u8> {
+        let mut pixel_data = vec![0xFE, 0xFF, 0x00, 0xE0, 0x00, 0x00, 0x00, 0x00];
+        pixel_data.extend_from_slice(&[0xFE, 0xFF, 0x00, 0xE0]);
+        pixel_data.extend_from_slice(&(fragment.len() as u32).to_le_bytes());
+        pixel_data.extend_from_slice(fragment);
+        pixel_data.extend_from_slice(&[0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00]);
+        pixel_data
+    }
+
+    #[test]
+    fn write_decompressed_pixels() {
+        use crate::types::CompressionScheme;
+
+        // 8 bits RLE, 1 segment with the 2 pixels 0 and 255.
+        let mut frame = vec![0u8; 64];
+        frame[0] = 1;
+        frame[4] = 64;
+        frame.extend_from_slice(&[0x01, 0x00, 0xFF, 0x80]);
+        let mut obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                us_element(Tag::x0028x0002, 1),
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 8),
+                us_element(Tag::x0028x0101, 8),
+            ],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Rle),
+        );
+        obj.pixel_data = Some(encapsulated(&frame).into());
+
+        let decompress = Writer::default().decompress_pixels(true);
+        let mut written = vec![];
+        decompress.write_object(&obj, &mut written).unwrap();
+        let parsed = crate::Parser::new(false).parse_object(&written).unwrap();
+        assert_eq!(TransferSyntax::little_endian_explicit(), parsed.transfer_syntax);
+        assert_eq!(Some(&[0, 255][..]), parsed.pixel_data.as_deref());
+        assert_eq!("DOE^JOHN", parsed.get::<String>(Tag::x0010x0010));
+        // The object itself is unchanged.
+        assert!(obj.transfer_syntax.is_encapsulated());
+
+        // JPEG2000 cannot be decoded: nothing is written, but it is kept as is by default.
+        let mut jpeg2000 = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0010, 1),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 8),
+            ],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossless),
+        );
+        jpeg2000.pixel_data = Some(encapsulated(&[0xFF, 0x4F, 0xFF, 0x51]).into());
+        let mut written = vec![];
+        assert!(matches!(
+            decompress.write_object(&jpeg2000, &mut written),
+            Err(DicomError::ImageFormatNotSupported)
+        ));
+        assert!(written.is_empty());
+        jpeg2000.write(&mut written).unwrap();
+        let parsed = crate::Parser::new(false).parse_object(&written).unwrap();
+        assert_eq!(jpeg2000.transfer_syntax, parsed.transfer_syntax);
+        assert_eq!(jpeg2000.pixel_data, parsed.pixel_data);
+    }
+}
@@ -1,86 +1,268 @@
+use nom::bytes::streaming::take;
 use nom::number::Endianness;
 use nom::IResult;
-use image::{ImageBuffer, GrayImage, Luma};
-use crate::img::{DicomImage, Gray16Image};
-use crate::parser::{parse_u16, parse_tag, parse_vr, parse_length};
-use crate::{Tag, TransferSyntax, types::CompressionScheme};
-use nom::combinator::cond;
+use image::{ImageBuffer, GrayImage, RgbImage};
+use crate::img::{DicomImage, Gray16Image, ImageGeometry, PhotometricInterpretation};
+use crate::parser::parse_u16;
+use crate::parser::pixel_data::{decode_rle_native, rle_segments};
+use crate::overlay::unpack_bits;
+use crate::parser::encapsulated::parse_encapsulated;
+use crate::{DicomError, DicomResult, TransferSyntax, types::CompressionScheme};
 use log::debug;
+use std::borrow::Cow;
 
-pub(crate) fn parse_image(buf: &[u8], transfer_syntax: TransferSyntax, rows: u16, columns: u16, bits_allocated: u16, bits_stored: u16) -> IResult<&[u8], DicomImage>{
-    // First need to consume the tag, vr and length.
+/// Decode the image from the value of the pixel data element. The planar configuration
+/// (x0028x0006) is only used for native color images: 0 when the samples of each pixel are
+/// interleaved, 1 when they are stored plane by plane.
+///
+/// MONOCHROME1 images are inverted (each value is subtracted from the maximum value of the bit
+/// depth) so that the minimum value is black, like MONOCHROME2 images.
+///
+/// When there is more than one frame, all the frames of native and RLE grayscale images are
+/// decoded. Only the first frame of color images is decoded.
+pub(crate) fn parse_image(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    geometry: &ImageGeometry,
+    planar_configuration: u16,
+    photometric_interpretation: &PhotometricInterpretation,
+    frames: u32,
+) -> DicomResult<DicomImage> {
+    let is_jpeg2000 = matches!(
+        transfer_syntax.compression_scheme,
+        Some(CompressionScheme::Jpeg2000Lossless) | Some(CompressionScheme::Jpeg2000Lossy)
+    );
+    let mut image = if frames > 1 && geometry.samples_per_pixel == 1 && !is_jpeg2000 {
+        decode_frames(buf, transfer_syntax, geometry, frames)?
+    } else {
+        decode_image(buf, transfer_syntax, geometry, planar_configuration)?
+    };
+
+    if *photometric_interpretation == PhotometricInterpretation::Monochrome1 {
+        match image {
+            DicomImage::Grayscale8 { ref mut image } => image::imageops::invert(image),
+            DicomImage::Grayscale16 { ref mut image } => image::imageops::invert(image),
+            DicomImage::MultiFrameGrayscale8 { ref mut frames } => {
+                frames.iter_mut().for_each(image::imageops::invert)
+            }
+            DicomImage::MultiFrameGrayscale16 { ref mut frames } => {
+                frames.iter_mut().for_each(image::imageops::invert)
+            }
+            _ => (),
+        }
+    }
+    Ok(image)
+}
+
+/// Decode the frames one after the other. RLE frames are decoded to native pixel data first.
+fn decode_frames(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    geometry: &ImageGeometry,
+    frames: u32,
+) -> DicomResult<DicomImage> {
+    let (native, endian) = match transfer_syntax.compression_scheme {
+        Some(CompressionScheme::Rle) => {
+            (Cow::Owned(decode_rle_native(buf, geometry, frames)?), Endianness::Little)
+        }
+        _ => (Cow::Borrowed(buf), transfer_syntax.endianness()),
+    };
+
+    let mut current = &native[..];
+    match geometry.bits_allocated {
+        8 => {
+            let mut images = Vec::with_capacity(frames as usize);
+            for _ in 0..frames {
+                let (rest, image) = parse_img_u8(current, geometry)?;
+                images.push(image);
+                current = rest;
+            }
+            Ok(DicomImage::MultiFrameGrayscale8 { frames: images })
+        }
+        16 => {
+            let mut images = Vec::with_capacity(frames as usize);
+            for _ in 0..frames {
+                let (rest, image) = parse_img_u16(current, endian, geometry)?;
+                images.push(image);
+                current = rest;
+            }
+            Ok(DicomImage::MultiFrameGrayscale16 { frames: images })
+        }
+        bits => Err(DicomError::BitsAllocatedNotSupported(bits)),
+    }
+}
+
+fn decode_image(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    geometry: &ImageGeometry,
+    planar_configuration: u16,
+) -> DicomResult<DicomImage> {
+    let ImageGeometry { rows, columns, bits_allocated, bits_stored, .. } = *geometry;
     debug!("Parse image: Rows {} Cols {}, Bits (allocated: {}/Stored {})", rows, columns, bits_allocated, bits_stored);
-    let (buf, tag) = parse_tag(buf, transfer_syntax.endianness())?;
-    assert!(tag == Tag::x7FE0x0010);
-    let (buf, vr) = cond(transfer_syntax.is_vr_explicit(), parse_vr)(buf)?;
-    let (buf, _) = parse_length(buf, &vr, transfer_syntax.endianness())?;
 
-    if let Some(CompressionScheme::Jpeg2000Lossless) = transfer_syntax.compression_scheme {
-        debug!("Image is in JPEG2000 format.");
-        return Ok((&[], DicomImage::Jpeg2000 { image: buf.to_vec() }))
+    match transfer_syntax.compression_scheme {
+        Some(CompressionScheme::Jpeg2000Lossless) | Some(CompressionScheme::Jpeg2000Lossy) => {
+            debug!("Image is in JPEG2000 format.");
+            return Ok(DicomImage::Jpeg2000 { image: buf.to_vec() })
+        }
+        Some(CompressionScheme::Rle) => {
+            debug!("Image is in RLE format.");
+            return parse_rle_image(buf, geometry);
+        }
+        None => (),
     }
 
-    debug!("Will parse {} bytes", columns as u32 * rows as u32 * bits_allocated as u32 /2);
     debug!("Remaining length of buffer = {}", buf.len());
+    if geometry.samples_per_pixel == 3 {
+        return match bits_allocated {
+            8 if planar_configuration == 1 => {
+                let interleaved = planar_to_interleaved(buf, geometry);
+                let (_, image) = parse_img_rgb8(&interleaved, geometry)?;
+                Ok(DicomImage::Rgb8 { image })
+            }
+            8 => {
+                let (_, image) = parse_img_rgb8(buf, geometry)?;
+                Ok(DicomImage::Rgb8 { image })
+            }
+            _ => Err(DicomError::ImageFormatNotSupported),
+        };
+    }
+
     // Depending on bits allocated, we need to read either 8 or 16 bytes.
     match bits_allocated {
+        1 => {
+            // Bitmap, the bits are packed without padding at the end of the rows.
+            let image = unpack_bits(buf, rows, columns)?;
+            Ok(DicomImage::Grayscale8 { image })
+        }
         8 => {
             //assert_eq!(rows as u32 *columns as u32 , length);
-            let (rest, image) = parse_img_u8(buf, rows, columns)?;
-            Ok((rest, DicomImage::Grayscale8 { image }))
+            let (_, image) = parse_img_u8(buf, geometry)?;
+            Ok(DicomImage::Grayscale8 { image })
         }
         16 => {
             //assert_eq!(rows as u32 *columns as u32, length/2);
-            let (rest, image) = parse_img_u16(buf, transfer_syntax.endianness(), rows, columns, bits_allocated, bits_stored)?;
-            Ok((rest, DicomImage::Grayscale16 { image }))
+            let (_, image) = parse_img_u16(buf, transfer_syntax.endianness(), geometry)?;
+            Ok(DicomImage::Grayscale16 { image })
         }
-        _ => panic!("Bits allocated not supported yet = {}", bits_allocated)
+        bits => Err(DicomError::BitsAllocatedNotSupported(bits)),
     }
 }
 
-fn parse_img_u8(buf: &[u8], rows: u16, columns: u16) -> IResult<&[u8], GrayImage> {
-    let mut img = ImageBuffer::new(columns as u32, rows as u32);
-    let mut current_buf = buf;
-    for y in 0..rows {
-        for x in 0..columns {
-            let (rest, grey_value) = nom::number::complete::be_u8(current_buf)?;
-            let pixel = img.get_pixel_mut(x as u32, y as u32);
-            *pixel = Luma([grey_value]);
-            current_buf = rest;
+/// RLE frames are always encoded with one segment per sample and per byte, whatever the planar
+/// configuration, so the channels are reassembled here.
+fn parse_rle_image(buf: &[u8], geometry: &ImageGeometry) -> DicomResult<DicomImage> {
+    let (_, pixel_data) = parse_encapsulated(buf)?;
+    let segments = rle_segments(&pixel_data, 0, geometry)?;
+    let pixel_count = geometry.pixel_count();
+
+    let (width, height) = (geometry.columns as u32, geometry.rows as u32);
+    match (geometry.samples_per_pixel, geometry.bits_allocated) {
+        (1, 8) => {
+            let data = segments[0][..pixel_count].to_vec();
+            let image = ImageBuffer::from_raw(width, height, data).expect("Buffer has the size of the image");
+            Ok(DicomImage::Grayscale8 { image })
         }
+        (1, 16) => {
+            // Most significant bytes are in the first segment.
+            let data: Vec<u8> = (0..pixel_count)
+                .flat_map(|i| vec![segments[0][i], segments[1][i]])
+                .collect();
+            let (_, image) = parse_img_u16(&data, Endianness::Big, geometry)?;
+            Ok(DicomImage::Grayscale16 { image })
+        }
+        (3, 8) => {
+            let data: Vec<u8> = (0..pixel_count)
+                .flat_map(|i| vec![segments[0][i], segments[1][i], segments[2][i]])
+                .collect();
+            let image = ImageBuffer::from_raw(width, height, data).expect("Buffer has the size of the image");
+            Ok(DicomImage::Rgb8 { image })
+        }
+        (samples, bits) => Err(DicomError::RleDecode(format!(
+            "{} samples of {} bits are not supported",
+            samples, bits
+        ))),
     }
-    Ok((current_buf, img))
 }
 
-fn parse_img_u16(buf: &[u8], endian: Endianness, rows: u16, columns: u16, bits_allocated: u16, bits_stored: u16) -> IResult<&[u8], Gray16Image> {
-    let mut img = ImageBuffer::new(columns as u32, rows as u32);
+/// Read the interleaved R, G, B samples of each pixel (planar configuration 0).
+fn parse_img_rgb8<'a>(buf: &'a [u8], geometry: &ImageGeometry) -> IResult<&'a [u8], RgbImage, DicomError> {
+    let (rest, data) = take(geometry.sample_count())(buf)?;
+    let image = ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, data.to_vec())
+        .expect("Buffer has the size of the image");
+    Ok((rest, image))
+}
+
+/// Reorder 8 bits samples stored plane by plane (planar configuration 1: all the red values, then
+/// all the green values and then all the blue values) so that the samples of each pixel are
+/// interleaved.
+fn planar_to_interleaved(buf: &[u8], geometry: &ImageGeometry) -> Vec<u8> {
+    let pixel_count = geometry.pixel_count();
+    let samples = geometry.samples_per_pixel as usize;
+    if buf.len() < pixel_count * samples {
+        return buf.to_vec();
+    }
+    (0..pixel_count)
+        .flat_map(|pixel| (0..samples).map(move |sample| buf[sample * pixel_count + pixel]))
+        .collect()
+}
+
+fn parse_img_u8<'a>(buf: &'a [u8], geometry: &ImageGeometry) -> IResult<&'a [u8], GrayImage, DicomError> {
+    let mut data = vec![0; geometry.pixel_count()];
+    let (rest, _) = parse_img_u8_into(buf, &mut data)?;
+    let image = ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, data)
+        .expect("Buffer has the size of the image");
+    Ok((rest, image))
+}
+
+/// Read one byte per pixel into `out`. `out` should have the same number of pixels as the image.
+pub(crate) fn parse_img_u8_into<'a>(buf: &'a [u8], out: &mut [u8]) -> IResult<&'a [u8], (), DicomError> {
     let mut current_buf = buf;
+    for pixel in out.iter_mut() {
+        let (rest, grey_value) = nom::number::complete::be_u8(current_buf)?;
+        *pixel = grey_value;
+        current_buf = rest;
+    }
+    Ok((current_buf, ()))
+}
 
-    for y in 0..rows {
-        for x in 0..columns {
-            let (rest, grey_value) = parse_u16(current_buf, endian)?;
-
-            let pixel = img.get_pixel_mut(x as u32, y as u32);
-            if bits_stored != 16 {
-                let diff = bits_allocated - bits_stored;
-                let mut mask = 0u16;
-                for _ in 0..diff {
-                    mask = (mask << 1) | 0b1;
-                }
-                let mask = mask << bits_stored;
-
-                let left: u16 = grey_value << diff;
-                let left = left | (left & mask) >> bits_stored;
-                *pixel = Luma([left]);
-            } else {
-                *pixel = Luma([grey_value]);
+fn parse_img_u16<'a>(buf: &'a [u8], endian: Endianness, geometry: &ImageGeometry) -> IResult<&'a [u8], Gray16Image, DicomError> {
+    let mut data = vec![0; geometry.pixel_count()];
+    let (rest, _) = parse_img_u16_into(buf, endian, geometry, &mut data)?;
+    let image = ImageBuffer::from_raw(geometry.columns as u32, geometry.rows as u32, data)
+        .expect("Buffer has the size of the image");
+    Ok((rest, image))
+}
+
+/// Read two bytes per pixel into `out`. `out` should have the same number of pixels as the image.
+pub(crate) fn parse_img_u16_into<'a>(buf: &'a [u8], endian: Endianness, geometry: &ImageGeometry, out: &mut [u16]) -> IResult<&'a [u8], (), DicomError> {
+    geometry.check_bits(16).map_err(nom::Err::Failure)?;
+    let ImageGeometry { bits_allocated, bits_stored, .. } = *geometry;
+    let mut current_buf = buf;
+
+    for pixel in out.iter_mut() {
+        let (rest, grey_value) = parse_u16(current_buf, endian)?;
+
+        if bits_stored != 16 {
+            let diff = bits_allocated - bits_stored;
+            let mut mask = 0u16;
+            for _ in 0..diff {
+                mask = (mask << 1) | 0b1;
             }
+            let mask = mask << bits_stored;
 
-            current_buf = rest;
+            let left: u16 = grey_value << diff;
+            let left = left | (left & mask) >> bits_stored;
+            *pixel = left;
+        } else {
+            *pixel = grey_value;
         }
+
+        current_buf = rest;
     }
 
-    Ok((current_buf, img))
+    Ok((current_buf, ()))
 }
 //
 //fn parse_imgbuf_u8<T>(reader: &mut T, rows: u16, columns: u16) -> DicomResult<GrayImage>
@@ -139,3 +321,181 @@ fn parse_img_u16(buf: &[u8], endian: Endianness, rows: u16, columns: u16, bits_a
 //    Ok(img)
 //}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::pixel_data::parse_pixel_data;
+
+    fn geometry(rows: u16, columns: u16, bits_allocated: u16, bits_stored: u16) -> ImageGeometry {
+        ImageGeometry { rows, columns, bits_allocated, bits_stored, samples_per_pixel: 1 }
+    }
+
+    #[test]
+    fn parse_jpeg2000_lossy() {
+        let mut data = vec![
+            0xE0, 0x7F, 0x10, 0x00, // pixel data
+            b'O', b'B', 0x00, 0x00, // OB
+            0xFF, 0xFF, 0xFF, 0xFF, // undefined length
+        ];
+        let codestream = vec![0xFF, 0x4F, 0xFF, 0x51];
+        data.extend_from_slice(&codestream);
+
+        let ts = TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossy);
+        let (_, pixel_data) = parse_pixel_data(&data, ts).unwrap();
+        let monochrome2 = PhotometricInterpretation::Monochrome2;
+        let res = parse_image(pixel_data, ts, &geometry(1, 1, 8, 8), 0, &monochrome2, 1);
+        assert!(res.is_ok());
+        let image = res.unwrap();
+        if let DicomImage::Jpeg2000 { image } = image {
+            assert_eq!(codestream, image);
+        } else {
+            panic!("Expected a JPEG2000 image, got {:?}", image);
+        }
+    }
+
+    #[test]
+    fn decode_into_reused_buffer() {
+        let geometry = geometry(2, 2, 16, 12);
+        let ts = TransferSyntax::little_endian_explicit();
+        let mut out = vec![0u16; 4];
+
+        let first: Vec<u8> = vec![0x01, 0x00, 0xFF, 0x0F, 0x00, 0x08, 0x34, 0x02];
+        let second: Vec<u8> = vec![0x10, 0x00, 0x20, 0x00, 0x30, 0x00, 0x40, 0x00];
+        for pixel_data in &[first, second] {
+            DicomImage::decode_u16_into(pixel_data, ts, &geometry, &mut out).unwrap();
+            let monochrome2 = PhotometricInterpretation::Monochrome2;
+            let image = parse_image(pixel_data, ts, &geometry, 0, &monochrome2, 1).unwrap();
+            if let DicomImage::Grayscale16 { image } = image {
+                assert_eq!(image.into_raw(), out);
+            } else {
+                panic!("Expected a 16 bits image, got {:?}", image);
+            }
+        }
+
+        let geometry = ImageGeometry { bits_allocated: 8, bits_stored: 8, ..geometry };
+        let mut out = vec![0u8; 4];
+        DicomImage::decode_u8_into(&[1, 2, 3, 4], &geometry, &mut out).unwrap();
+        assert_eq!(vec![1, 2, 3, 4], out);
+        let res = DicomImage::decode_u8_into(&[1, 2, 3, 4], &geometry, &mut [0u8; 3]);
+        assert!(res.is_err());
+
+        // Bits stored cannot be bigger than bits allocated.
+        let geometry = ImageGeometry { bits_allocated: 16, bits_stored: 17, ..geometry };
+        let res = DicomImage::decode_u16_into(&[0; 8], ts, &geometry, &mut [0u16; 4]);
+        assert!(matches!(res, Err(DicomError::InvalidBitsStored(17, 16))));
+        let geometry = ImageGeometry { bits_allocated: 8, bits_stored: 0, ..geometry };
+        let res = DicomImage::decode_u8_into(&[0; 4], &geometry, &mut [0u8; 4]);
+        assert!(matches!(res, Err(DicomError::InvalidBitsStored(0, 8))));
+    }
+
+    #[test]
+    fn parse_rle_rgb() {
+        let mut frame = vec![0u8; 64];
+        frame[0] = 3; // number of segments
+        frame[4] = 64;
+        frame[8] = 67;
+        frame[12] = 69;
+        frame.extend_from_slice(&[0x01, 255, 0]); // red, literal run
+        frame.extend_from_slice(&[0xFF, 0]); // green, replicate run
+        frame.extend_from_slice(&[0x01, 0, 255]); // blue, literal run
+
+        let mut data = vec![0xFE, 0xFF, 0x00, 0xE0, 0x00, 0x00, 0x00, 0x00]; // empty offset table
+        data.extend_from_slice(&[0xFE, 0xFF, 0x00, 0xE0]);
+        data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        data.extend_from_slice(&frame);
+        data.extend_from_slice(&[0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00]);
+
+        let ts = TransferSyntax::with_compression_scheme(CompressionScheme::Rle);
+        let geometry = ImageGeometry { samples_per_pixel: 3, ..geometry(1, 2, 8, 8) };
+        let rgb = PhotometricInterpretation::Rgb;
+        let image = parse_image(&data, ts, &geometry, 0, &rgb, 1).unwrap();
+        if let DicomImage::Rgb8 { image } = image {
+            assert_eq!(vec![255, 0, 0, 0, 0, 255], image.into_raw());
+        } else {
+            panic!("Expected a RGB image, got {:?}", image);
+        }
+    }
+
+    #[test]
+    fn parse_native_rgb() {
+        let geometry = ImageGeometry { samples_per_pixel: 3, ..geometry(2, 2, 8, 8) };
+        let ts = TransferSyntax::little_endian_explicit();
+        let data = vec![
+            255, 0, 0, 0, 255, 0, // red, green
+            0, 0, 255, 10, 20, 30, // blue, dark
+        ];
+        let rgb = PhotometricInterpretation::Rgb;
+        let image = parse_image(&data, ts, &geometry, 0, &rgb, 1).unwrap();
+        if let DicomImage::Rgb8 { image } = image {
+            assert_eq!(&image::Rgb([255, 0, 0]), image.get_pixel(0, 0));
+            assert_eq!(&image::Rgb([0, 255, 0]), image.get_pixel(1, 0));
+            assert_eq!(&image::Rgb([0, 0, 255]), image.get_pixel(0, 1));
+            assert_eq!(&image::Rgb([10, 20, 30]), image.get_pixel(1, 1));
+        } else {
+            panic!("Expected a RGB image, got {:?}", image);
+        }
+
+        // Same pixels, plane by plane.
+        let planar = vec![255, 0, 0, 10, 0, 255, 0, 20, 0, 0, 255, 30];
+        let interleaved = parse_image(&data, ts, &geometry, 0, &rgb, 1).unwrap();
+        let planar = parse_image(&planar, ts, &geometry, 1, &rgb, 1).unwrap();
+        match (interleaved, planar) {
+            (DicomImage::Rgb8 { image: interleaved }, DicomImage::Rgb8 { image: planar }) => {
+                assert_eq!(interleaved.into_raw(), planar.into_raw())
+            }
+            images => panic!("Expected RGB images, got {:?}", images),
+        }
+
+        let geometry = ImageGeometry { bits_allocated: 16, bits_stored: 16, ..geometry };
+        let res = parse_image(&[0; 24], ts, &geometry, 0, &PhotometricInterpretation::Rgb, 1);
+        assert!(matches!(res, Err(DicomError::ImageFormatNotSupported)));
+    }
+
+    #[test]
+    fn invert_monochrome1() {
+        let ts = TransferSyntax::little_endian_explicit();
+        let monochrome1 = PhotometricInterpretation::Monochrome1;
+        let monochrome2 = PhotometricInterpretation::Monochrome2;
+
+        let geometry = geometry(1, 3, 8, 8);
+        let data = vec![0, 100, 255];
+        match (
+            parse_image(&data, ts, &geometry, 0, &monochrome1, 1).unwrap(),
+            parse_image(&data, ts, &geometry, 0, &monochrome2, 1).unwrap(),
+        ) {
+            (DicomImage::Grayscale8 { image: inverted }, DicomImage::Grayscale8 { image }) => {
+                assert_eq!(vec![0, 100, 255], image.into_raw());
+                assert_eq!(vec![255, 155, 0], inverted.into_raw());
+            }
+            images => panic!("Expected 8 bits images, got {:?}", images),
+        }
+
+        let geometry = ImageGeometry { bits_allocated: 16, bits_stored: 16, ..geometry };
+        let data = vec![0x00, 0x00, 0x10, 0x00, 0xFF, 0xFF];
+        match (
+            parse_image(&data, ts, &geometry, 0, &monochrome1, 1).unwrap(),
+            parse_image(&data, ts, &geometry, 0, &monochrome2, 1).unwrap(),
+        ) {
+            (DicomImage::Grayscale16 { image: inverted }, DicomImage::Grayscale16 { image }) => {
+                assert_eq!(vec![0, 0x10, 0xFFFF], image.into_raw());
+                assert_eq!(vec![0xFFFF, 0xFFEF, 0], inverted.into_raw());
+            }
+            images => panic!("Expected 16 bits images, got {:?}", images),
+        }
+    }
+
+    #[test]
+    fn parse_bitmap() {
+        let ts = TransferSyntax::little_endian_explicit();
+        let monochrome2 = PhotometricInterpretation::Monochrome2;
+        let image = parse_image(&[0b1000_0101], ts, &geometry(1, 8, 1, 1), 0, &monochrome2, 1).unwrap();
+        if let DicomImage::Grayscale8 { image } = image {
+            assert_eq!(vec![255, 0, 255, 0, 0, 0, 0, 255], image.into_raw());
+        } else {
+            panic!("Expected a 8 bits image, got {:?}", image);
+        }
+
+        let res = parse_image(&[0; 4], ts, &geometry(1, 1, 32, 32), 0, &monochrome2, 1);
+        assert!(matches!(res, Err(DicomError::BitsAllocatedNotSupported(32))));
+    }
+}
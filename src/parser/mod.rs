@@ -1,6 +1,6 @@
 //! All the functions to parse the DICOM.
 
-use crate::{Tag, ValueRepresentation};
+use crate::{DicomError, Tag, ValueRepresentation};
 use nom::bytes::streaming::take;
 use nom::character::streaming::one_of;
 use nom::number::streaming::{be_u16, be_u32, le_u16, le_u32};
@@ -8,26 +8,31 @@ use nom::number::Endianness;
 use nom::IResult;
 
 mod element;
+pub mod encode;
+pub(crate) mod encapsulated;
 pub mod obj;
+#[cfg(feature = "image")]
 pub(crate) mod image;
+pub(crate) mod pixel_data;
+mod rle;
 pub mod sq;
 
 /// Normal value of a data element is just a number of bytes.
-fn parse_data(buf: &[u8], length: u32) -> IResult<&[u8], &[u8]> {
+fn parse_data(buf: &[u8], length: u32) -> IResult<&[u8], &[u8], DicomError> {
     take(length)(buf)
 }
 
 /// A tag is made of two u16: the group and the element.
 ///
 /// some tags are known from the standard and added to the library.
-fn parse_tag(buf: &[u8], endian: Endianness) -> IResult<&[u8], Tag> {
+fn parse_tag(buf: &[u8], endian: Endianness) -> IResult<&[u8], Tag, DicomError> {
     let (rest, group) = parse_u16(buf, endian)?;
     let (rest, element) = parse_u16(rest, endian)?;
     Ok((rest, Tag::from_values(group, element)))
 }
 
 /// Parse a 4 bytes unsigned integer according to the endianness
-fn parse_u32(buf: &[u8], endian: Endianness) -> IResult<&[u8], u32> {
+fn parse_u32(buf: &[u8], endian: Endianness) -> IResult<&[u8], u32, DicomError> {
     match endian {
         Endianness::Little => le_u32(buf),
         Endianness::Big => be_u32(buf),
@@ -35,7 +40,7 @@ fn parse_u32(buf: &[u8], endian: Endianness) -> IResult<&[u8], u32> {
 }
 
 /// Parse a 2 bytes unsigned integer according to the endianness
-fn parse_u16(buf: &[u8], endian: Endianness) -> IResult<&[u8], u16> {
+fn parse_u16(buf: &[u8], endian: Endianness) -> IResult<&[u8], u16, DicomError> {
     match endian {
         Endianness::Little => le_u16(buf),
         Endianness::Big => be_u16(buf),
@@ -43,7 +48,7 @@ fn parse_u16(buf: &[u8], endian: Endianness) -> IResult<&[u8], u16> {
 }
 
 /// Value Representation is encoded as two characters (ascii).
-fn parse_vr(buf: &[u8]) -> IResult<&[u8], ValueRepresentation> {
+fn parse_vr(buf: &[u8]) -> IResult<&[u8], ValueRepresentation, DicomError> {
     let (rest, first_char) = one_of(VR_CHARS)(buf)?;
     let (rest, second_char) = one_of(VR_CHARS)(rest)?;
     Ok((
@@ -55,12 +60,12 @@ fn parse_vr(buf: &[u8]) -> IResult<&[u8], ValueRepresentation> {
 /// Depending on whether there is a VR, the length is parsed differently:
 /// - No VR => 4 bytes
 /// - VR => normal case, 2 bytes,
-///         special case, 2 bytes padding + 4 bytes of length.
+///   special case, 2 bytes padding + 4 bytes of length.
 fn parse_length<'buf>(
     buf: &'buf [u8],
     vr: &Option<ValueRepresentation>,
     endian: Endianness,
-) -> IResult<&'buf [u8], u32> {
+) -> IResult<&'buf [u8], u32, DicomError> {
     match vr {
         Some(vr) => {
             if vr.has_special_length() {
@@ -96,6 +101,12 @@ mod tests {
         assert_eq!(rest.len(), 3);
     }
 
+    #[test]
+    pub fn test_signed_short_vr() {
+        let (_, vr) = parse_vr("SS".as_bytes()).unwrap();
+        assert_eq!(ValueRepresentation::SS, vr);
+    }
+
     #[test]
     pub fn test_unknown_vr() {
         let vr_str = "ul123".as_bytes();
@@ -1,16 +1,26 @@
-use super::element::{parse_dataelement};
-use crate::types::DataElement;
-use crate::parser::{parse_tag, image::parse_image};
+use super::element::{parse_dataelement, parse_dataelement_with_depth, MAX_NESTING_DEPTH};
+use crate::types::{DataElement, FromDicomValue};
+use crate::parser::{parse_tag, pixel_data::parse_pixel_data};
 use crate::{Tag, TransferSyntax, DicomObject, DicomError};
+#[cfg(feature = "image")]
+use crate::{
+    img::{DicomImage, PhotometricInterpretation},
+    parser::image::parse_image,
+    DicomResult,
+};
 use log::debug;
 use nom::bytes::streaming::{tag, take};
 use nom::combinator::peek;
 use nom::number::Endianness;
 use nom::IResult;
+use std::borrow::Cow;
 use std::convert::TryFrom;
 
 /// Header is just 132 bytes of padding + the value DICM.
-fn parse_header(buf: &[u8]) -> IResult<&[u8], ()> {
+fn parse_header(buf: &[u8]) -> IResult<&[u8], (), DicomError> {
+    if buf.len() < 132 {
+        return Err(nom::Err::Failure(DicomError::CannotReadHeader(buf.len())));
+    }
     let (buf, _) = take(128usize)(buf)?;
     let (buf, _) = tag("DICM")(buf)?;
     Ok((buf, ()))
@@ -45,10 +55,11 @@ enum ParserState {
 /// let mut parser = Parser::default();
 /// let res = parser.parse_object(&content);
 ///
-/// if let Ok((_, dcm)) = res {
+/// if let Ok(dcm) = res {
 ///     // dcm contains the Dicom object. Its lifetime is bound to the content vec.
 ///
-///     // Save the image data.
+///     // Save the image data, when the `image` feature is enabled.
+///     #[cfg(feature = "image")]
 ///     if let Some(ref img) = dcm.image {
 ///         img.save("somewhere.png").unwrap();
 ///     }
@@ -59,13 +70,20 @@ enum ParserState {
 /// }
 /// ```
 pub struct Parser {
+    #[cfg_attr(not(feature = "image"), allow(dead_code))]
     parse_image: bool,
+    require_magic: bool,
+    probe_endianness: bool,
+    max_nesting_depth: usize,
 }
 
 impl Default for Parser {
     fn default() -> Self {
         Self {
             parse_image: true,
+            require_magic: true,
+            probe_endianness: false,
+            max_nesting_depth: MAX_NESTING_DEPTH,
         }
     }
 }
@@ -74,12 +92,38 @@ impl Parser {
 
     /// Create a new parser. if `parse_image` is true, the images will be parsed and returned in the
     /// `DicomObject`. Otherwise, only the tags that are before the image data tag will be parsed.
+    /// Images are never parsed without the `image` feature.
     pub fn new(parse_image: bool) -> Self {
         Self {
-            parse_image
+            parse_image,
+            ..Self::default()
         }
     }
 
+    /// If false, files without the preamble and the `DICM` magic are accepted. The content is
+    /// then parsed from the start of the buffer, either from the group 2 if present or directly
+    /// from the data set, which is then expected in implicit VR little endian.
+    pub fn require_magic(mut self, require_magic: bool) -> Self {
+        self.require_magic = require_magic;
+        self
+    }
+
+    /// If true, the data set is also parsed with the other endianness when it cannot be parsed
+    /// with the endianness of the transfer syntax or when the rows (x0028x0010) are missing. This
+    /// recovers files which declared transfer syntax is wrong. The transfer syntax of the object
+    /// is the one that was used to parse the data set.
+    pub fn probe_endianness(mut self, probe_endianness: bool) -> Self {
+        self.probe_endianness = probe_endianness;
+        self
+    }
+
+    /// Maximum number of nested sequences, 64 by default. Deeper sequences are rejected with
+    /// `DicomError::NestingTooDeep`.
+    pub fn max_nesting_depth(mut self, max_nesting_depth: usize) -> Self {
+        self.max_nesting_depth = max_nesting_depth;
+        self
+    }
+
     /// Parse the DICOM object.
     ///
     /// Will return a `DicomObject` which has the same lifetime as the input slice.
@@ -94,43 +138,70 @@ impl Parser {
             let (next_state, next_buf) = match state {
                 ParserState::Header => {
                     debug!("Parse header");
-                    let (buf, _) = parse_header(current_buf)?;
-                    (ParserState::Group2, buf)
+                    match parse_header(current_buf) {
+                        Ok((buf, _)) => (ParserState::Group2, buf),
+                        Err(e) if self.require_magic => return Err(e.into()),
+                        Err(_) => {
+                            debug!("No DICM magic, parse the file as preamble-less");
+                            let buf = current_buf.strip_prefix(b"DICM".as_ref()).unwrap_or(current_buf);
+                            let (_, next_tag) = peek(|i| parse_tag(i, Endianness::Little))(buf)?;
+                            if next_tag.get_group() == 2 {
+                                (ParserState::Group2, buf)
+                            } else {
+                                obj = Some(DicomObject::new(vec![], TransferSyntax::little_endian_implicit()));
+                                (ParserState::Content, buf)
+                            }
+                        }
+                    }
                 }
                 ParserState::Group2 => {
                     debug!("Parse group 2");
-                    let (buf, (transfer_syntax, elements)) = parse_group2(current_buf)?;
+                    let (rest, (transfer_syntax, elements)) = parse_group2(current_buf)?;
                     debug!("Transfer syntax is {:?}", transfer_syntax);
-                    obj = Some(DicomObject::new(elements, transfer_syntax));
-                    (ParserState::Content, buf)
+                    let mut parsed = DicomObject::new(elements, transfer_syntax);
+                    parsed.meta = Some(Cow::Borrowed(&buf[..buf.len() - rest.len()]));
+                    obj = Some(parsed);
+                    (ParserState::Content, rest)
                 }
                 ParserState::Content => {
                     debug!("Parse content");
                     let obj = obj.as_mut().unwrap();
-                    let (buf, elements) = parse_content(current_buf, obj.transfer_syntax)?;
+                    let max_depth = self.max_nesting_depth;
+                    let mut content = parse_content(current_buf, obj.transfer_syntax, max_depth);
+                    let is_plausible = matches!(content, Ok((_, ref elements)) if has_rows(elements, obj.transfer_syntax));
+                    if self.probe_endianness && !is_plausible {
+                        let swapped = obj.transfer_syntax.with_endianness(match obj.transfer_syntax.endianness() {
+                            Endianness::Little => Endianness::Big,
+                            Endianness::Big => Endianness::Little,
+                        });
+                        match parse_content(current_buf, swapped, max_depth) {
+                            Ok((buf, elements)) if has_rows(&elements, swapped) => {
+                                debug!("Data set is encoded in {:?}", swapped.endianness());
+                                obj.transfer_syntax = swapped;
+                                content = Ok((buf, elements));
+                            }
+                            _ => debug!("Data set cannot be parsed in {:?} either", swapped.endianness()),
+                        }
+                    }
+                    let (buf, elements) = content?;
                     obj.append(elements);
                     (ParserState::Images, buf)
                 }
+                ParserState::Images if current_buf.is_empty() => {
+                    // No pixel data, for example when it is provided by a URL (x0028x7FE0).
+                    debug!("No pixel data");
+                    (ParserState::Finished, current_buf)
+                }
                 ParserState::Images => {
+                    let obj = obj.as_mut().unwrap();
+                    let (buf, pixel_data) = parse_pixel_data(current_buf, obj.transfer_syntax)?;
+                    obj.pixel_data = Some(Cow::Borrowed(pixel_data));
 
+                    #[cfg(feature = "image")]
                     if self.parse_image {
-                        let obj = obj.as_mut().unwrap();
-                        let rows: u16 = obj.try_get(Tag::x0028x0010).unwrap();
-                        let cols: u16 = obj.try_get(Tag::x0028x0011).unwrap();
-                        // TODO image with colors
-                        let _samples_per_pixel: u16 = obj.try_get(Tag::x0028x0002).unwrap();
-                        let _nb_of_frames: Result<u16, _> = obj.try_get(Tag::x0028x0008);
-                        let _representation: Result<String, _> = obj.try_get(Tag::x0028x0004);
-
-                        let bits_stored: u16 = obj.try_get(Tag::x0028x0101).unwrap();
-                        let bits_allocated: u16 = obj.try_get(Tag::x0028x0100).unwrap();
-
-                        let (buf, image) = parse_image(current_buf, obj.transfer_syntax, rows, cols, bits_allocated, bits_stored)?;
-                        obj.image = Some(image);
-                        (ParserState::Finished, buf)
-                    } else {
-                        (ParserState::Finished, buf)
+                        obj.image = decode_image(obj, pixel_data)?;
                     }
+                    (ParserState::Finished, buf)
                 },
                 ParserState::Finished => break,
             };
@@ -139,11 +210,53 @@ impl Parser {
             current_buf = next_buf;
         }
 
-        Ok(obj.unwrap())
+        let mut obj = obj.unwrap();
+        obj.bytes_consumed = buf.len() - current_buf.len();
+        Ok(obj)
+    }
+}
+
+/// Decode the image from the value of the pixel data element. None for the color images that
+/// are not RGB, as other color spaces (YBR...) are not converted yet. Native pixel data shorter
+/// than `DicomObject::expected_pixel_bytes` is rejected before decoding.
+#[cfg(feature = "image")]
+fn decode_image(obj: &DicomObject, pixel_data: &[u8]) -> DicomResult<Option<DicomImage>> {
+    let geometry = obj.image_geometry()?;
+    let representation = obj.photometric_interpretation().unwrap_or(
+        if geometry.samples_per_pixel == 3 {
+            PhotometricInterpretation::Rgb
+        } else {
+            PhotometricInterpretation::Monochrome2
+        },
+    );
+
+    let is_rgb = representation == PhotometricInterpretation::Rgb;
+    if geometry.samples_per_pixel == 3 && !is_rgb {
+        debug!("Photometric interpretation {:?} not supported", representation);
+        return Ok(None);
+    }
+
+    if !obj.transfer_syntax.is_encapsulated() {
+        if let Some(expected) = obj.expected_pixel_bytes() {
+            if (pixel_data.len() as u64) < expected {
+                return Err(DicomError::ImageBufferSize(expected as usize, pixel_data.len()));
+            }
+        }
     }
+
+    let planar_configuration = obj.try_get(Tag::x0028x0006).unwrap_or(0);
+    let image = parse_image(
+        pixel_data,
+        obj.transfer_syntax,
+        &geometry,
+        planar_configuration,
+        &representation,
+        obj.number_of_frames(),
+    )?;
+    Ok(Some(image))
 }
 
-fn parse_group2(buf: &[u8]) -> IResult<&[u8], (TransferSyntax, Vec<DataElement>)> {
+fn parse_group2(buf: &[u8]) -> IResult<&[u8], (TransferSyntax, Vec<DataElement<'_>>), DicomError> {
     let mut ts = None;
 
     let mut current_buf = buf;
@@ -159,39 +272,296 @@ fn parse_group2(buf: &[u8]) -> IResult<&[u8], (TransferSyntax, Vec<DataElement>)
         let (buf, data_element) =
             parse_dataelement(buf, TransferSyntax::little_endian_explicit())?;
         if data_element.tag == Tag::x0002x0010 {
-            ts = Some(TransferSyntax::try_from(&data_element.data).unwrap());
+            ts = Some(TransferSyntax::try_from(&data_element.data).map_err(nom::Err::Failure)?);
         }
 
         group2_elements.push(data_element);
         current_buf = buf;
     }
 
-    Ok((
-        current_buf,
-        (
-            ts.expect("There should be the transfer syntax in group 2."),
-            group2_elements,
-        ),
-    ))
+    let ts = ts.ok_or(nom::Err::Failure(DicomError::MissingTag(Tag::x0002x0010)))?;
+    Ok((current_buf, (ts, group2_elements)))
+}
+
+/// Sentinel used to probe the endianness: the rows are found and are not 0.
+fn has_rows(elements: &[DataElement], transfer_syntax: TransferSyntax) -> bool {
+    elements
+        .iter()
+        .find(|el| el.tag == Tag::x0028x0010)
+        .and_then(|el| u16::from_element(el, &transfer_syntax).ok())
+        .is_some_and(|rows| rows > 0)
 }
 
-fn parse_content(buf: &[u8], transfer_syntax: TransferSyntax) -> IResult<&[u8], Vec<DataElement>> {
+fn parse_content(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    max_depth: usize,
+) -> IResult<&[u8], Vec<DataElement<'_>>, DicomError> {
     let mut current_buf = buf;
     let mut elements = vec![];
 
     let endian = transfer_syntax.endianness();
 
-    loop {
-        // Will stop if next tag is for images.
+    // Will stop at the end of the data set or if next tag is for images.
+    while !current_buf.is_empty() {
         let (buf, next_tag) = peek(|i| parse_tag(i, endian))(current_buf)?;
         if next_tag == Tag::x7FE0x0010 {
             break;
         }
 
-        let (buf, data_element) = parse_dataelement(buf, transfer_syntax)?;
+        let (buf, data_element) = parse_dataelement_with_depth(buf, transfer_syntax, max_depth)?;
         elements.push(data_element);
         current_buf = buf;
     }
 
     Ok((current_buf, elements))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_files::fixture;
+    use crate::types::PersonName;
+
+    #[test]
+    fn parse_without_magic() {
+        // Remove the preamble and DICM, the file starts with group 2.
+        let content = fixture("DOE^JOHN", &[])[132..].to_vec();
+        assert!(Parser::default().parse_object(&content).is_err());
+
+        let obj = Parser::default().require_magic(false).parse_object(&content).unwrap();
+        assert_eq!(TransferSyntax::little_endian_explicit(), obj.transfer_syntax);
+        let name: PersonName = obj.get(Tag::x0010x0010);
+        assert_eq!(vec!["DOE", "JOHN"], name.0);
+    }
+
+    #[test]
+    fn parse_pixel_data_url() {
+        use crate::test_files::explicit_le;
+
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.4.94"));
+        content.extend(explicit_le(0x0028, 0x0010, "US", &2u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x7FE0, "UR", b"http://jpip.example.com/1.2.3 "));
+
+        let obj = Parser::default().parse_object(&content).unwrap();
+        assert_eq!(Some("http://jpip.example.com/1.2.3".to_string()), obj.pixel_data_url());
+        assert!(obj.pixel_data.is_none());
+        #[cfg(feature = "image")]
+        assert!(obj.image.is_none());
+    }
+
+    #[test]
+    fn keep_meta_bytes() {
+        let content = fixture("DOE^JOHN", &[]);
+        let obj = Parser::default().parse_object(&content).unwrap();
+        let meta = obj.meta_bytes().unwrap();
+        assert_eq!(&[0; 128][..], &meta[..128]);
+        assert_eq!(b"DICM", &meta[128..132]);
+        // Only the transfer syntax is in group 2.
+        assert_eq!(132 + 8 + 20, meta.len());
+        assert_eq!(&content[..meta.len()], meta);
+    }
+
+    #[test]
+    fn outlive_parsed_buffer() {
+        let obj = {
+            let content = fixture("DOE^JOHN", &[]);
+            let obj = Parser::default().parse_object(&content).unwrap();
+            obj.into_owned()
+        };
+        let name: PersonName = obj.get(Tag::x0010x0010);
+        assert_eq!(vec!["DOE", "JOHN"], name.0);
+        assert_eq!(Some(&[0, 64, 128, 255][..]), obj.pixel_data.as_deref());
+        #[cfg(feature = "image")]
+        assert!(obj.image.is_some());
+    }
+
+    #[cfg(not(feature = "image"))]
+    #[test]
+    fn parse_metadata_without_image() {
+        let content = fixture("DOE^JOHN", &[]);
+        let obj = Parser::default().parse_object(&content).unwrap();
+        let name: PersonName = obj.get(Tag::x0010x0010);
+        assert_eq!(vec!["DOE", "JOHN"], name.0);
+        // The pixel data is still available without decoding the image.
+        assert_eq!(Some(&[0, 64, 128, 255][..]), obj.pixel_data.as_deref());
+        assert_eq!(vec![0, 64, 128, 255], obj.stored_values().unwrap());
+    }
+
+    #[test]
+    fn count_bytes_consumed() {
+        let mut content = fixture("DOE^JOHN", &[]);
+        let obj = Parser::default().parse_object(&content).unwrap();
+        assert_eq!(content.len(), obj.bytes_consumed);
+
+        // Trailing data after the pixel data is not parsed.
+        let length = content.len();
+        content.extend_from_slice(&[0; 6]);
+        let obj = Parser::default().parse_object(&content).unwrap();
+        assert_eq!(length, obj.bytes_consumed);
+    }
+
+    #[test]
+    fn parse_without_pixel_data() {
+        use crate::test_files::explicit_le;
+
+        // Structured report: no image, the content ends with a sequence.
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+        content.extend(explicit_le(0x0010, 0x0010, "PN", b"DOE^JOHN"));
+        content.extend(explicit_le(0x0040, 0xA040, "CS", b"CONTAINER "));
+        content.extend(explicit_le(0x0040, 0xA043, "SQ", &[]));
+
+        let obj = Parser::default().parse_object(&content).unwrap();
+        #[cfg(feature = "image")]
+        assert!(obj.image.is_none());
+        assert!(obj.pixel_data.is_none());
+        assert_eq!(Some(Tag::x0040xA043), obj.elements().last().map(|el| el.tag));
+        let name: PersonName = obj.get(Tag::x0010x0010);
+        assert_eq!(vec!["DOE", "JOHN"], name.0);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn report_unsupported_bits_allocated() {
+        use crate::test_files::explicit_le;
+
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+        content.extend(explicit_le(0x0028, 0x0010, "US", &1u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0011, "US", &1u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0100, "US", &32u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0101, "US", &32u16.to_le_bytes()));
+        content.extend(explicit_le(0x7FE0, 0x0010, "OB", &[0, 0, 0, 1]));
+
+        match Parser::default().parse_object(&content) {
+            Err(DicomError::BitsAllocatedNotSupported(32)) => (),
+            res => panic!("Expected BitsAllocatedNotSupported, got {:?}", res),
+        }
+        // The tags can still be read without the image.
+        let obj = Parser::new(false).parse_object(&content).unwrap();
+        assert!(matches!(obj.stored_values(), Err(DicomError::BitsAllocatedNotSupported(32))));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn reject_truncated_pixel_data() {
+        use crate::test_files::explicit_le;
+
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+        content.extend(explicit_le(0x0028, 0x0010, "US", &2u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0011, "US", &2u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0100, "US", &16u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0101, "US", &16u16.to_le_bytes()));
+        content.extend(explicit_le(0x7FE0, 0x0010, "OW", &[0; 6]));
+
+        match Parser::default().parse_object(&content) {
+            Err(DicomError::ImageBufferSize(8, 6)) => (),
+            res => panic!("Expected ImageBufferSize, got {:?}", res),
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn parse_multi_frame() {
+        use crate::test_files::explicit_le;
+
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+        content.extend(explicit_le(0x0028, 0x0008, "IS", b"3 "));
+        content.extend(explicit_le(0x0028, 0x0010, "US", &2u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0011, "US", &2u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0100, "US", &16u16.to_le_bytes()));
+        content.extend(explicit_le(0x0028, 0x0101, "US", &16u16.to_le_bytes()));
+        let pixels: Vec<u8> = (0..12u16).flat_map(|v| (v * 100).to_le_bytes()).collect();
+        content.extend(explicit_le(0x7FE0, 0x0010, "OW", &pixels));
+
+        let obj = Parser::default().parse_object(&content).unwrap();
+        let frames = obj.frames().unwrap();
+        assert_eq!(3, frames.len());
+        assert_eq!(vec![0, 100, 200, 300], frames[0].clone().into_raw());
+        assert_eq!(vec![800, 900, 1000, 1100], frames[2].clone().into_raw());
+        assert!(obj.frames8().is_none());
+
+        // A single frame is returned as one frame.
+        let content = fixture("DOE^JOHN", &[]);
+        let obj = Parser::default().parse_object(&content).unwrap();
+        assert_eq!(1, obj.frames8().unwrap().len());
+        assert!(obj.frames().is_none());
+    }
+
+    #[test]
+    fn parse_short_preamble() {
+        let content = vec![0; 50];
+        match Parser::default().parse_object(&content) {
+            Err(DicomError::CannotReadHeader(50)) => (),
+            res => panic!("Expected CannotReadHeader, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn probe_swapped_endianness() {
+        use crate::test_files::explicit_le;
+        use crate::parser::encode::encode_dataelement;
+        use crate::types::Value;
+        use crate::ValueRepresentation;
+
+        // Declared as explicit little endian but the data set is in big endian.
+        let mut content = vec![0; 128];
+        content.extend_from_slice(b"DICM");
+        content.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+        let big_endian = TransferSyntax::big_endian_explicit();
+        let us = |tag, value: u16| DataElement {
+            tag,
+            vr: Some(ValueRepresentation::US),
+            length: 2,
+            data: Value::Owned(value.to_be_bytes().to_vec()),
+        };
+        for el in [
+            us(Tag::x0028x0010, 2),
+            us(Tag::x0028x0011, 3),
+            DataElement {
+                tag: Tag::x7FE0x0010,
+                vr: Some(ValueRepresentation::OB),
+                length: 6,
+                data: Value::Owned(vec![1, 2, 3, 4, 5, 6]),
+            },
+        ] {
+            content.extend(encode_dataelement(&el, big_endian).unwrap());
+        }
+
+        assert!(Parser::new(false).parse_object(&content).is_err());
+
+        let obj = Parser::new(false)
+            .probe_endianness(true)
+            .parse_object(&content)
+            .unwrap();
+        assert_eq!(big_endian, obj.transfer_syntax);
+        assert_eq!(2, obj.get::<u16>(Tag::x0028x0010));
+        assert_eq!(3, obj.get::<u16>(Tag::x0028x0011));
+        assert_eq!(Some(&[1, 2, 3, 4, 5, 6][..]), obj.pixel_data.as_deref());
+    }
+
+    #[test]
+    fn parse_raw_data_set() {
+        let mut content = vec![
+            0x10, 0x00, 0x10, 0x00, // patient name
+            0x08, 0x00, 0x00, 0x00, // length is four bytes when no VR
+        ];
+        content.extend_from_slice(b"DOE^JOHN");
+        content.extend_from_slice(&[0xE0, 0x7F, 0x10, 0x00, 0x02, 0x00, 0x00, 0x00, 0xAA, 0xBB]);
+
+        let obj = Parser::new(false).require_magic(false).parse_object(&content).unwrap();
+        assert_eq!(TransferSyntax::little_endian_implicit(), obj.transfer_syntax);
+        let name: PersonName = obj.get(Tag::x0010x0010);
+        assert_eq!(vec!["DOE", "JOHN"], name.0);
+        assert_eq!(Some(&[0xAA, 0xBB][..]), obj.pixel_data.as_deref());
+    }
+}
@@ -0,0 +1,65 @@
+//! Value of the pixel data element. This does not depend on the `image` feature so that the
+//! pixel data can be read, and RLE pixel data decompressed, without decoding images.
+use crate::img::ImageGeometry;
+use crate::parser::encapsulated::{parse_encapsulated, EncapsulatedPixelData};
+use crate::parser::{parse_length, parse_tag, parse_vr, rle};
+use crate::{DicomError, DicomResult, Tag, TransferSyntax};
+use nom::bytes::streaming::take;
+use nom::combinator::cond;
+use nom::IResult;
+
+/// Parse the pixel data element (x7FE0x0010) and return its value. When the pixel data is
+/// encapsulated, the length is undefined and the value is the rest of the buffer.
+pub(crate) fn parse_pixel_data(buf: &[u8], transfer_syntax: TransferSyntax) -> IResult<&[u8], &[u8], DicomError> {
+    // First need to consume the tag, vr and length.
+    let (buf, tag) = parse_tag(buf, transfer_syntax.endianness())?;
+    assert!(tag == Tag::x7FE0x0010);
+    let (buf, vr) = cond(transfer_syntax.is_vr_explicit(), parse_vr)(buf)?;
+    let (buf, length) = parse_length(buf, &vr, transfer_syntax.endianness())?;
+
+    if length == u32::MAX {
+        Ok((&[], buf))
+    } else {
+        take(length)(buf)
+    }
+}
+
+/// Decode the segments of a RLE frame and check that there is one segment per byte of each
+/// sample.
+pub(crate) fn rle_segments(pixel_data: &EncapsulatedPixelData, index: usize, geometry: &ImageGeometry) -> DicomResult<Vec<Vec<u8>>> {
+    let frame = pixel_data
+        .frame(index)
+        .ok_or_else(|| DicomError::RleDecode(format!("No fragment for frame {}", index)))?;
+    let segments = rle::decode_frame(&frame)?;
+
+    let pixel_count = geometry.pixel_count();
+    let expected_segments = geometry.samples_per_pixel as usize * geometry.bits_allocated as usize / 8;
+    if segments.len() != expected_segments || segments.iter().any(|s| s.len() < pixel_count) {
+        return Err(DicomError::RleDecode(format!(
+            "Expected {} segments of {} bytes",
+            expected_segments, pixel_count
+        )));
+    }
+    Ok(segments)
+}
+
+/// Decode all the frames of RLE pixel data into native pixel data: little endian, with the
+/// samples of each pixel interleaved (planar configuration 0).
+pub(crate) fn decode_rle_native(buf: &[u8], geometry: &ImageGeometry, frames: u32) -> DicomResult<Vec<u8>> {
+    let (_, pixel_data) = parse_encapsulated(buf)?;
+    let bytes_per_sample = geometry.bits_allocated as usize / 8;
+    let mut native = Vec::with_capacity(frames as usize * geometry.sample_count() * bytes_per_sample);
+
+    for index in 0..frames as usize {
+        let segments = rle_segments(&pixel_data, index, geometry)?;
+        // Segments of a sample start with the most significant byte.
+        let order: Vec<&[u8]> = (0..geometry.samples_per_pixel as usize)
+            .flat_map(|sample| (0..bytes_per_sample).rev().map(move |byte| sample * bytes_per_sample + byte))
+            .map(|segment| &segments[segment][..])
+            .collect();
+        for pixel in 0..geometry.pixel_count() {
+            native.extend(order.iter().map(|segment| segment[pixel]));
+        }
+    }
+    Ok(native)
+}
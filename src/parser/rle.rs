@@ -0,0 +1,89 @@
+//! RLE compression (transfer syntax 1.2.840.10008.1.2.5).
+//!
+//! Each frame is made of a 64 bytes header followed by up to 15 segments. The header contains the
+//! number of segments and the offset of each segment. The pixels are split in one segment per
+//! byte of each sample: a RGB 8 bits image has three segments (R, G, B) and a 16 bits grayscale
+//! image has two segments (most significant bytes first). Segments are encoded with PackBits.
+//!
+//! See http://dicom.nema.org/dicom/2013/output/chtml/part05/chapter_G.html
+use crate::{DicomError, DicomResult};
+use byteorder::{ByteOrder, LittleEndian};
+
+const HEADER_LEN: usize = 64;
+const MAX_SEGMENTS: usize = 15;
+
+/// Decode a RLE frame and return its segments.
+pub(crate) fn decode_frame(frame: &[u8]) -> DicomResult<Vec<Vec<u8>>> {
+    if frame.len() < HEADER_LEN {
+        return Err(DicomError::RleDecode(format!(
+            "Frame should have at least {} bytes (got {})",
+            HEADER_LEN,
+            frame.len()
+        )));
+    }
+
+    let nb_segments = LittleEndian::read_u32(frame) as usize;
+    if nb_segments > MAX_SEGMENTS {
+        return Err(DicomError::RleDecode(format!("Too many segments = {}", nb_segments)));
+    }
+
+    let offsets: Vec<usize> = (0..nb_segments)
+        .map(|i| LittleEndian::read_u32(&frame[4 + 4 * i..]) as usize)
+        .collect();
+
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = offsets.get(i + 1).cloned().unwrap_or(frame.len());
+            if start < HEADER_LEN || start > end || end > frame.len() {
+                return Err(DicomError::RleDecode(format!(
+                    "Invalid segment offsets {}..{}",
+                    start, end
+                )));
+            }
+            decode_segment(&frame[start..end])
+        })
+        .collect()
+}
+
+/// PackBits decoding. Each run starts with a byte n:
+/// - 0 <= n <= 127: copy the next n + 1 bytes,
+/// - -127 <= n <= -1: repeat the next byte 1 - n times,
+/// - n = -128: nothing to do.
+fn decode_segment(data: &[u8]) -> DicomResult<Vec<u8>> {
+    let mut decoded = vec![];
+    let mut i = 0;
+    while i < data.len() {
+        let n = data[i] as i8;
+        i += 1;
+        if n >= 0 {
+            let end = i + n as usize + 1;
+            let literal = data
+                .get(i..end)
+                .ok_or_else(|| DicomError::RleDecode("Segment ended during a literal run".to_string()))?;
+            decoded.extend_from_slice(literal);
+            i = end;
+        } else if n != -128 {
+            let value = *data
+                .get(i)
+                .ok_or_else(|| DicomError::RleDecode("Segment ended during a replicate run".to_string()))?;
+            decoded.extend(std::iter::repeat(value).take((1 - n as isize) as usize));
+            i += 1;
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_packbits() {
+        let data = vec![0x02, 1, 2, 3, 0xFD, 9, 0x80, 0x00, 4];
+        assert_eq!(vec![1, 2, 3, 9, 9, 9, 9, 4], decode_segment(&data).unwrap());
+        assert!(decode_segment(&[0x03, 1]).is_err());
+    }
+}
@@ -11,39 +11,84 @@
 //! and Sequence Delimitation Item (FFFE,E0DD). However, the Data Set within the Value Field of
 //! the Data Element Item (FFFE,E000) shall be encoded according to the rules conveyed by the Transfer Syntax.
 
-use crate::types::DataElement;
-use crate::TransferSyntax;
+use crate::types::{DataElement, FromDicomValue};
+use crate::{DicomError, DicomResult, TransferSyntax};
+use nom::bytes::streaming::take;
 use nom::IResult;
 use crate::parser::{parse_tag, parse_length};
 use crate::Tag;
 use nom::combinator::peek;
-use nom::number::Endianness;
 use log::debug;
-use crate::parser::element::parse_dataelement;
+use crate::parser::element::{parse_dataelement, parse_dataelement_with_depth};
 
 /// An item is a list of data elements.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Item<'buf> {
     pub elements: Vec<DataElement<'buf>>,
+    /// Transfer syntax the elements were parsed with. It is the one of the object, except in UN
+    /// sequences which items are always in implicit VR little endian.
+    pub transfer_syntax: TransferSyntax,
 }
 
-/// A sequence is a list of items. Special sequence elements are always using little endian implicit (no VR)
+impl<'buf> Item<'buf> {
+    pub fn into_owned(self) -> Item<'static> {
+        Item {
+            elements: self.elements.into_iter().map(DataElement::into_owned).collect(),
+            transfer_syntax: self.transfer_syntax,
+        }
+    }
+
+    pub fn get_element(&self, tag: Tag) -> Option<&DataElement<'buf>> {
+        self.elements.iter().find(|el| el.tag == tag)
+    }
+
+    /// Same as `DicomObject::try_get`, with the transfer syntax of the item.
+    pub fn try_get<T: FromDicomValue>(&self, tag: Tag) -> DicomResult<T> {
+        match self.get_element(tag) {
+            Some(el) => FromDicomValue::from_element(el, &self.transfer_syntax),
+            None => Err(DicomError::NoSuchTag(tag)),
+        }
+    }
+}
+
+/// A sequence is a list of items. Special sequence elements have no VR and use the endianness of
+/// the transfer syntax.
 /// A sequence with undefined length is finished by the special element xFFFExE0DD.
 ///
-/// The buffer here only contains the data part of the SQ data element (the rest has already been
-/// parsed).
+/// A sequence with a defined length has no delimitation: its items are exactly `length` bytes.
 ///
-/// TODO Length defined.
-pub(crate) fn parse_seq(buf: &[u8], _length: u32, transfer_syntax: TransferSyntax) -> IResult<&[u8], Vec<Item>> {
+/// The buffer here only contains the data part of the SQ data element (the rest has already been
+/// parsed). `max_depth` is the number of nested sequences that can still be parsed, including
+/// this one.
+pub(crate) fn parse_seq(
+    buf: &[u8],
+    length: u32,
+    transfer_syntax: TransferSyntax,
+    max_depth: usize,
+) -> IResult<&[u8], Vec<Item<'_>>, DicomError> {
+    let max_depth = max_depth
+        .checked_sub(1)
+        .ok_or(nom::Err::Failure(DicomError::NestingTooDeep))?;
+
+    if length != u32::MAX {
+        let (rest, mut content) = take(length)(buf)?;
+        let mut items = vec![];
+        while !content.is_empty() {
+            let (buf, item) = parse_item(content, transfer_syntax, max_depth)?;
+            content = buf;
+            items.push(item);
+        }
+        return Ok((rest, items));
+    }
 
     let mut current = buf;
     let mut items = vec![];
     'parse_loop: loop {
-        let (_, next_tag) = peek(|i| parse_tag(i, Endianness::Little))(current)?;
+        let (_, next_tag) = peek(|i| parse_tag(i, transfer_syntax.endianness()))(current)?;
         match next_tag {
             Tag::xFFFExE000 => {
                 // Item !
-                let (buf, item) = parse_item(current, transfer_syntax)?;
+                let (buf, item) = parse_item(current, transfer_syntax, max_depth)?;
                 current = buf;
                 items.push(item);
             },
@@ -53,7 +98,12 @@ pub(crate) fn parse_seq(buf: &[u8], _length: u32, transfer_syntax: TransferSynta
                 current = buf;
                 break 'parse_loop;
             },
-            _ => panic!("Unexpected tag {:?}", next_tag),
+            tag => {
+                return Err(nom::Err::Failure(DicomError::ParseError(format!(
+                    "Expected an item or a sequence delimitation but got {:?}",
+                    tag
+                ))))
+            }
         }
     }
 
@@ -67,14 +117,23 @@ pub(crate) fn parse_seq(buf: &[u8], _length: u32, transfer_syntax: TransferSynta
 /// | TAG | LENGTH | DATA |
 /// | 4   | 4      \ n    |
 ///
-pub(crate) fn parse_item(buf: &[u8], transfer_syntax: TransferSyntax) -> IResult<&[u8], Item> {
+/// `max_depth` is the number of sequences that can be nested in the elements of the item.
+pub(crate) fn parse_item(
+    buf: &[u8],
+    transfer_syntax: TransferSyntax,
+    max_depth: usize,
+) -> IResult<&[u8], Item<'_>, DicomError> {
 
     let (buf, tag) = parse_tag(buf, transfer_syntax.endianness())?;
-    // FIXME error handling.
-    assert_eq!(Tag::xFFFExE000, tag);
+    if tag != Tag::xFFFExE000 {
+        return Err(nom::Err::Failure(DicomError::ParseError(format!(
+            "Expected an item but got {:?}",
+            tag
+        ))));
+    }
     let (buf, length) = parse_length(buf, &None, transfer_syntax.endianness())?;
 
-    let is_len_undefined = length == std::u32::MAX;
+    let is_len_undefined = length == u32::MAX;
 
     // will parse the content of an item. An item contains a buf of data elements.
     let mut current = buf;
@@ -87,7 +146,7 @@ pub(crate) fn parse_item(buf: &[u8], transfer_syntax: TransferSyntax) -> IResult
         // Stop condition.
         if is_len_undefined {
             // Expect to have a Item delimitation element
-            let (_, next_tag) = peek(|i| parse_tag(i, Endianness::Little))(current)?;
+            let (_, next_tag) = peek(|i| parse_tag(i, transfer_syntax.endianness()))(current)?;
             if next_tag == Tag::xFFFExE00D {
                 debug!("Found Item delimitation tag");
                 let (buf, _) = parse_dataelement(current, TransferSyntax::little_endian_implicit())?;
@@ -100,24 +159,28 @@ pub(crate) fn parse_item(buf: &[u8], transfer_syntax: TransferSyntax) -> IResult
 
 
         let length_before = current.len();
-        let (buf, data_element) = parse_dataelement(current, transfer_syntax)?;
+        let (buf, data_element) = parse_dataelement_with_depth(current, transfer_syntax, max_depth)?;
         let parsed_len = length_before - buf.len();
-        remaining_len -= parsed_len;
+        remaining_len = remaining_len
+            .checked_sub(parsed_len)
+            .ok_or(nom::Err::Failure(DicomError::ItemLengthOverrun(data_element.tag)))?;
         elements.push(data_element);
 
         current = buf;
     }
 
-    Ok((current, Item { elements }))
+    Ok((current, Item { elements, transfer_syntax }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::element::MAX_NESTING_DEPTH;
+
     #[test]
     fn parse_item_implicitlength() {
         let data: Vec<u8> = vec![
-            0xFE, 0xFF, 0x00, 0xE0, // item start, always little endian
+            0xFE, 0xFF, 0x00, 0xE0, // item start
             0xFF, 0xFF, 0xFF, 0xFF, // undefined length.
             0x08, 0x00, 0x00, 0x00, 0x55, 0x4c, 0x04, 0x00, 0x30, 0x00, 0x00, 0x00, 0x08, 0x00,
             0x00, 0x01, 0x53, 0x48, 0x08, 0x00, 0x54, 0x2d, 0x31, 0x31, 0x35, 0x30, 0x33, 0x20,
@@ -130,11 +193,97 @@ mod tests {
         let res = parse_item(
             &data,
             TransferSyntax::little_endian_explicit(),
+            MAX_NESTING_DEPTH,
         );
 
         assert!(res.is_ok());
-        let (_, item)  = res.unwrap();
-        println!("{:?}", item);
-        assert!(false);
+        let (rest, item)  = res.unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(4, item.elements.len());
+        assert_eq!(Tag::x0008x0104, item.elements[3].tag);
+        assert_eq!(TransferSyntax::little_endian_explicit(), item.transfer_syntax);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_defined_length_sequence() {
+        let item: Vec<u8> = vec![
+            0xFE, 0xFF, 0x00, 0xE0, 0x10, 0x00, 0x00, 0x00, // item of 16 bytes
+            0x08, 0x00, 0x00, 0x01, 0x53, 0x48, 0x08, 0x00, 0x54, 0x2d, 0x30, 0x34, 0x30, 0x30,
+            0x30, 0x20,
+        ];
+        let mut data: Vec<u8> = vec![
+            0x40, 0x00, 0x43, 0xA0, b'S', b'Q', 0x00, 0x00, 0x30, 0x00, 0x00, 0x00, // 48 bytes
+        ];
+        data.extend_from_slice(&item);
+        data.extend_from_slice(&item);
+        data.extend_from_slice(&[0x10, 0x00, 0x10, 0x00]); // next element
+
+        let (rest, el) = parse_dataelement(&data, TransferSyntax::little_endian_explicit()).unwrap();
+        assert_eq!(&[0x10, 0x00, 0x10, 0x00], rest);
+        let items = el.items().unwrap();
+        assert_eq!(2, items.len());
+        for item in items {
+            assert_eq!(1, item.elements.len());
+            assert_eq!(Tag::x0008x0100, item.elements[0].tag);
+        }
+    }
+
+    #[test]
+    fn parse_sequence_with_garbage_tag() {
+        let data: Vec<u8> = vec![
+            0x10, 0x00, 0x10, 0x00, 0x04, 0x00, 0x00, 0x00, b'J', b'O', b'H', b'N',
+        ];
+        assert!(matches!(
+            parse_seq(&data, u32::MAX, TransferSyntax::little_endian_explicit(), MAX_NESTING_DEPTH),
+            Err(nom::Err::Failure(DicomError::ParseError(_)))
+        ));
+        assert!(matches!(
+            parse_seq(&data, 12, TransferSyntax::little_endian_explicit(), MAX_NESTING_DEPTH),
+            Err(nom::Err::Failure(DicomError::ParseError(_)))
+        ));
+    }
+
+    #[test]
+    fn parse_item_element_overrun() {
+        let data: Vec<u8> = vec![
+            0xFE, 0xFF, 0x00, 0xE0, // item start
+            0x0A, 0x00, 0x00, 0x00, // length of 10 bytes but the element has 16.
+            0x08, 0x00, 0x00, 0x01, 0x53, 0x48, 0x08, 0x00, 0x54, 0x2d, 0x30, 0x34, 0x30, 0x30,
+            0x30, 0x20,
+        ];
+
+        match parse_item(&data, TransferSyntax::little_endian_explicit(), MAX_NESTING_DEPTH) {
+            Err(nom::Err::Failure(DicomError::ItemLengthOverrun(Tag::x0008x0100))) => (),
+            res => panic!("Expected an overrun error, got {:?}", res),
+        }
+    }
+
+    /// Sequences of undefined length nested `depth` times, the innermost item is empty.
+    fn nested_sequences(depth: usize) -> Vec<u8> {
+        let mut data = vec![];
+        for _ in 0..depth {
+            data.extend_from_slice(&[0x40, 0x00, 0x30, 0xA7, b'S', b'Q', 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF]);
+            data.extend_from_slice(&[0xFE, 0xFF, 0x00, 0xE0, 0xFF, 0xFF, 0xFF, 0xFF]);
+        }
+        for _ in 0..depth {
+            data.extend_from_slice(&[0xFE, 0xFF, 0x0D, 0xE0, 0x00, 0x00, 0x00, 0x00]);
+            data.extend_from_slice(&[0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00]);
+        }
+        data
+    }
+
+    #[test]
+    fn reject_deep_nesting() {
+        let ts = TransferSyntax::little_endian_explicit();
+        let data = nested_sequences(100);
+        assert!(matches!(
+            parse_dataelement(&data, ts),
+            Err(nom::Err::Failure(DicomError::NestingTooDeep))
+        ));
+
+        let (rest, _) = parse_dataelement_with_depth(&data, ts, 100).unwrap();
+        assert!(rest.is_empty());
+        let data = nested_sequences(MAX_NESTING_DEPTH);
+        assert!(parse_dataelement(&data, ts).is_ok());
+    }
+}
@@ -0,0 +1,108 @@
+//! Spatial Registration objects describe how to map the coordinates of one Frame of Reference to
+//! another, for example to fuse a PET series with a CT series.
+//!
+//! Each item of the Registration Sequence (x0070x0308) references a Frame of Reference and
+//! contains the matrices in its Matrix Registration Sequence (x0070x0309). The matrices are in the
+//! Matrix Sequence (x0070x030A) of each item.
+use crate::types::trim_padding;
+use crate::{DicomError, DicomObject, DicomResult, Tag};
+use std::convert::TryInto;
+
+/// A rigid (or affine) registration of a Frame of Reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registration {
+    /// Frame of Reference that is registered.
+    pub frame_of_reference_uid: String,
+    /// 4x4 transformation matrices, in row-major order. They are applied in order to transform
+    /// the coordinates of the registered Frame of Reference into the one of the registration
+    /// object.
+    pub matrices: Vec<[f64; 16]>,
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the registrations of a Spatial Registration object. Empty if the object has no
+    /// Registration Sequence.
+    pub fn registrations(&self) -> DicomResult<Vec<Registration>> {
+        let items = match self.get_element(Tag::x0070x0308).and_then(|el| el.items()) {
+            Some(items) => items,
+            None => return Ok(vec![]),
+        };
+
+        let mut registrations = vec![];
+        for item in items {
+            let frame_of_reference_uid: String =
+                item.try_get(Tag::x0020x0052)?;
+
+            let mut matrices = vec![];
+            let matrix_items = item
+                .get_element(Tag::x0070x0309)
+                .and_then(|el| el.items())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|item| item.get_element(Tag::x0070x030A))
+                .filter_map(|el| el.items())
+                .flatten();
+            for matrix_item in matrix_items {
+                let values: Vec<f64> = matrix_item.try_get(Tag::x3006x00C6)?;
+                let matrix = values.try_into().map_err(|values: Vec<f64>| {
+                    DicomError::ParseError(format!(
+                        "Transformation matrix should have 16 values but got {}",
+                        values.len()
+                    ))
+                })?;
+                matrices.push(matrix);
+            }
+
+            registrations.push(Registration {
+                frame_of_reference_uid: trim_padding(&frame_of_reference_uid).to_string(),
+                matrices,
+            });
+        }
+
+        Ok(registrations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{sequence, text_element};
+    use crate::TransferSyntax;
+
+    #[test]
+    fn read_registration() {
+        let matrix = sequence(
+            Tag::x0070x030A,
+            vec![vec![
+                text_element(Tag::x0070x030C, "RIGID "),
+                text_element(
+                    Tag::x3006x00C6,
+                    "1\\0\\0\\10.5\\0\\1\\0\\-2\\0\\0\\1\\3.25\\0\\0\\0\\1 ",
+                ),
+            ]],
+        );
+        let registration = sequence(
+            Tag::x0070x0308,
+            vec![vec![
+                text_element(Tag::x0020x0052, "1.2.3.4\0"),
+                sequence(Tag::x0070x0309, vec![vec![matrix]]),
+            ]],
+        );
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0020x0052, "1.2.3.5\0"), registration],
+            TransferSyntax::little_endian_implicit(),
+        );
+
+        assert_eq!(Some("1.2.3.5".to_string()), obj.frame_of_reference_uid());
+        assert_eq!(
+            vec![Registration {
+                frame_of_reference_uid: "1.2.3.4".to_string(),
+                matrices: vec![[
+                    1.0, 0.0, 0.0, 10.5, 0.0, 1.0, 0.0, -2.0, 0.0, 0.0, 1.0, 3.25, 0.0, 0.0, 0.0,
+                    1.0
+                ]],
+            }],
+            obj.registrations().unwrap()
+        );
+    }
+}
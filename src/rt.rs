@@ -0,0 +1,215 @@
+//! Radiotherapy objects (RT Structure Set, RT Dose...).
+//!
+//! The contours of an RT Structure Set are in the ROI Contour Sequence (x3006x0039), one item
+//! per ROI. Each ROI has a Contour Sequence (x3006x0040) of planar polygons which points are
+//! stored as x, y, z triplets in the Contour Data (x3006x0050), in the patient coordinates (mm).
+//!
+//! An RT Dose stores the dose grid as a multi-frame image, one frame per plane. The pixel values
+//! are multiplied by the Dose Grid Scaling (x3004x000E) to get the dose in Gy.
+use crate::{DicomError, DicomObject, DicomResult, Tag};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use nom::number::Endianness;
+
+/// The contours of one ROI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    /// ROI Number of the ROI in the Structure Set ROI Sequence.
+    pub referenced_roi_number: Option<i32>,
+    /// Polygons of the ROI, as lists of 3D points.
+    pub polygons: Vec<Vec<[f64; 3]>>,
+}
+
+/// The dose grid of an RT Dose.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoseGrid {
+    pub rows: usize,
+    pub columns: usize,
+    /// Grid Frame Offset Vector (x3004x000C): position of each frame along the normal of the
+    /// image plane, in mm.
+    pub frame_offsets: Vec<f64>,
+    /// Doses in Gy, frame by frame then row by row.
+    pub values: Vec<f64>,
+}
+
+impl DoseGrid {
+    pub fn frames(&self) -> usize {
+        self.frame_offsets.len()
+    }
+
+    /// Return the dose of a voxel.
+    pub fn get(&self, frame: usize, row: usize, column: usize) -> Option<f64> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+        self.values
+            .get((frame * self.rows + row) * self.columns + column)
+            .copied()
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the contours of an RT Structure Set, one entry per ROI. Empty if the object has no
+    /// ROI Contour Sequence.
+    pub fn rt_contours(&self) -> DicomResult<Vec<Contour>> {
+        let rois = match self.get_element(Tag::x3006x0039).and_then(|el| el.items()) {
+            Some(rois) => rois,
+            None => return Ok(vec![]),
+        };
+
+        let mut contours = vec![];
+        for roi in rois {
+            let mut polygons = vec![];
+            let items = roi
+                .get_element(Tag::x3006x0040)
+                .and_then(|el| el.items())
+                .unwrap_or_default();
+            for item in items {
+                let data: Vec<f64> = item.try_get(Tag::x3006x0050)?;
+                if data.len() % 3 != 0 {
+                    return Err(DicomError::ParseError(format!(
+                        "Contour data should have 3 values per point but got {}",
+                        data.len()
+                    )));
+                }
+                polygons.push(data.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect());
+            }
+
+            contours.push(Contour {
+                referenced_roi_number: roi.try_get(Tag::x3006x0084).ok(),
+                polygons,
+            });
+        }
+
+        Ok(contours)
+    }
+
+    /// Return the dose grid of an RT Dose, with the doses scaled to Gy. The Modality LUT, if any,
+    /// is applied before the scaling.
+    pub fn dose_grid(&self) -> DicomResult<DoseGrid> {
+        let geometry = self.image_geometry()?;
+        let frame_offsets: Vec<f64> = self.try_get(Tag::x3004x000C)?;
+        let scaling: f64 = self.try_get(Tag::x3004x000E)?;
+        let signed = self.try_get::<u16>(Tag::x0028x0103).unwrap_or(0) == 1;
+        let data = self
+            .pixel_data
+            .as_deref()
+            .ok_or(DicomError::NoSuchTag(Tag::x7FE0x0010))?;
+
+        let little_endian = self.transfer_syntax.endianness() == Endianness::Little;
+        let read = |width: usize, read_le: fn(&[u8]) -> f64, read_be: fn(&[u8]) -> f64| {
+            let read = if little_endian { read_le } else { read_be };
+            data.chunks_exact(width).map(read).collect::<Vec<_>>()
+        };
+        let values = match (geometry.bits_allocated, signed) {
+            (16, false) => read(
+                2,
+                |c| LittleEndian::read_u16(c) as f64,
+                |c| BigEndian::read_u16(c) as f64,
+            ),
+            (16, true) => read(
+                2,
+                |c| LittleEndian::read_i16(c) as f64,
+                |c| BigEndian::read_i16(c) as f64,
+            ),
+            (32, false) => read(
+                4,
+                |c| LittleEndian::read_u32(c) as f64,
+                |c| BigEndian::read_u32(c) as f64,
+            ),
+            (32, true) => read(
+                4,
+                |c| LittleEndian::read_i32(c) as f64,
+                |c| BigEndian::read_i32(c) as f64,
+            ),
+            (bits, _) => {
+                return Err(DicomError::ParseError(format!(
+                    "Dose grid with {} bits allocated is not supported",
+                    bits
+                )))
+            }
+        };
+
+        let values = match self.modality_lut()? {
+            Some(lut) => values.into_iter().map(|v| lut.apply(v as i32) as f64).collect(),
+            None => values,
+        };
+
+        let voxels = geometry.pixel_count() * frame_offsets.len();
+        if values.len() != voxels {
+            return Err(DicomError::ImageBufferSize(voxels, values.len()));
+        }
+
+        Ok(DoseGrid {
+            rows: geometry.rows as usize,
+            columns: geometry.columns as usize,
+            frame_offsets,
+            values: values.into_iter().map(|v| v * scaling).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{sequence, text_element, us_element};
+    use crate::TransferSyntax;
+
+    #[test]
+    fn read_dose_grid() {
+        let mut obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0010, 2),
+                us_element(Tag::x0028x0011, 2),
+                us_element(Tag::x0028x0100, 32),
+                us_element(Tag::x0028x0101, 32),
+                us_element(Tag::x0028x0103, 0),
+                text_element(Tag::x3004x000C, "0\\2.5 "),
+                text_element(Tag::x3004x000E, "0.5 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        let pixels: Vec<u8> = (0u32..8)
+            .flat_map(|v| (v * 10).to_le_bytes().to_vec())
+            .collect();
+        obj.pixel_data = Some(pixels.into());
+
+        let grid = obj.dose_grid().unwrap();
+        assert_eq!(2, grid.frames());
+        assert_eq!(vec![0.0, 2.5], grid.frame_offsets);
+        assert_eq!(
+            vec![0.0, 5.0, 10.0, 15.0, 20.0, 25.0, 30.0, 35.0],
+            grid.values
+        );
+        assert_eq!(Some(30.0), grid.get(1, 1, 0));
+        assert_eq!(None, grid.get(2, 0, 0));
+        assert_eq!(None, grid.get(0, 0, 2));
+    }
+
+    #[test]
+    fn read_triangle() {
+        let contour = sequence(
+            Tag::x3006x0040,
+            vec![vec![text_element(
+                Tag::x3006x0050,
+                "0\\0\\-10\\10.5\\0\\-10\\0\\20\\-10 ",
+            )]],
+        );
+        let rois = sequence(
+            Tag::x3006x0039,
+            vec![vec![contour, text_element(Tag::x3006x0084, "3 ")]],
+        );
+        let obj = DicomObject::new(vec![rois], TransferSyntax::little_endian_implicit());
+
+        assert_eq!(
+            vec![Contour {
+                referenced_roi_number: Some(3),
+                polygons: vec![vec![
+                    [0.0, 0.0, -10.0],
+                    [10.5, 0.0, -10.0],
+                    [0.0, 20.0, -10.0]
+                ]],
+            }],
+            obj.rt_contours().unwrap()
+        );
+    }
+}
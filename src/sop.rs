@@ -0,0 +1,122 @@
+//! SOP Classes (x0008x0016) tell what kind of object a file contains: an image, a structured
+//! report... The known SOP Class UIDs are mapped to a few categories to route the objects.
+//!
+//! See http://dicom.nema.org/medical/dicom/current/output/chtml/part04/sect_B.5.html
+use crate::types::trim_padding;
+use crate::{DicomObject, Tag};
+
+/// Category of a SOP Class.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SopCategory {
+    Image,
+    StructuredReport,
+    PresentationState,
+    /// RT objects that are not images: structure sets, plans, doses...
+    Radiotherapy,
+    Waveform,
+    /// PDF, CDA... documents encapsulated in a DICOM object.
+    EncapsulatedDocument,
+    /// Spatial registrations and fiducials.
+    Registration,
+    /// Unknown SOP Class UID, or no SOP Class UID.
+    Other,
+}
+
+const SOP_CLASSES: &[(&str, SopCategory)] = &[
+    // Images
+    ("1.2.840.10008.5.1.4.1.1.1", SopCategory::Image), // Computed Radiography
+    ("1.2.840.10008.5.1.4.1.1.1.1", SopCategory::Image), // Digital X-Ray For Presentation
+    ("1.2.840.10008.5.1.4.1.1.1.1.1", SopCategory::Image), // Digital X-Ray For Processing
+    ("1.2.840.10008.5.1.4.1.1.1.2", SopCategory::Image), // Digital Mammography For Presentation
+    ("1.2.840.10008.5.1.4.1.1.1.2.1", SopCategory::Image), // Digital Mammography For Processing
+    ("1.2.840.10008.5.1.4.1.1.2", SopCategory::Image), // CT
+    ("1.2.840.10008.5.1.4.1.1.2.1", SopCategory::Image), // Enhanced CT
+    ("1.2.840.10008.5.1.4.1.1.3.1", SopCategory::Image), // Ultrasound Multi-frame
+    ("1.2.840.10008.5.1.4.1.1.4", SopCategory::Image), // MR
+    ("1.2.840.10008.5.1.4.1.1.4.1", SopCategory::Image), // Enhanced MR
+    ("1.2.840.10008.5.1.4.1.1.6.1", SopCategory::Image), // Ultrasound
+    ("1.2.840.10008.5.1.4.1.1.7", SopCategory::Image), // Secondary Capture
+    ("1.2.840.10008.5.1.4.1.1.12.1", SopCategory::Image), // X-Ray Angiographic
+    ("1.2.840.10008.5.1.4.1.1.12.2", SopCategory::Image), // X-Ray Radiofluoroscopic
+    ("1.2.840.10008.5.1.4.1.1.20", SopCategory::Image), // Nuclear Medicine
+    ("1.2.840.10008.5.1.4.1.1.77.1.4", SopCategory::Image), // VL Photographic
+    ("1.2.840.10008.5.1.4.1.1.128", SopCategory::Image), // PET
+    ("1.2.840.10008.5.1.4.1.1.130", SopCategory::Image), // Enhanced PET
+    ("1.2.840.10008.5.1.4.1.1.481.1", SopCategory::Image), // RT Image
+    // Structured reports
+    ("1.2.840.10008.5.1.4.1.1.88.11", SopCategory::StructuredReport), // Basic Text SR
+    ("1.2.840.10008.5.1.4.1.1.88.22", SopCategory::StructuredReport), // Enhanced SR
+    ("1.2.840.10008.5.1.4.1.1.88.33", SopCategory::StructuredReport), // Comprehensive SR
+    ("1.2.840.10008.5.1.4.1.1.88.34", SopCategory::StructuredReport), // Comprehensive 3D SR
+    ("1.2.840.10008.5.1.4.1.1.88.50", SopCategory::StructuredReport), // Mammography CAD SR
+    ("1.2.840.10008.5.1.4.1.1.88.59", SopCategory::StructuredReport), // Key Object Selection
+    ("1.2.840.10008.5.1.4.1.1.88.67", SopCategory::StructuredReport), // X-Ray Radiation Dose SR
+    // Presentation states
+    ("1.2.840.10008.5.1.4.1.1.11.1", SopCategory::PresentationState), // Grayscale Softcopy
+    ("1.2.840.10008.5.1.4.1.1.11.2", SopCategory::PresentationState), // Color Softcopy
+    ("1.2.840.10008.5.1.4.1.1.11.3", SopCategory::PresentationState), // Pseudo-Color Softcopy
+    ("1.2.840.10008.5.1.4.1.1.11.4", SopCategory::PresentationState), // Blending Softcopy
+    // Radiotherapy
+    ("1.2.840.10008.5.1.4.1.1.481.2", SopCategory::Radiotherapy), // RT Dose
+    ("1.2.840.10008.5.1.4.1.1.481.3", SopCategory::Radiotherapy), // RT Structure Set
+    ("1.2.840.10008.5.1.4.1.1.481.4", SopCategory::Radiotherapy), // RT Beams Treatment Record
+    ("1.2.840.10008.5.1.4.1.1.481.5", SopCategory::Radiotherapy), // RT Plan
+    ("1.2.840.10008.5.1.4.1.1.481.8", SopCategory::Radiotherapy), // RT Ion Plan
+    // Waveforms
+    ("1.2.840.10008.5.1.4.1.1.9.1.1", SopCategory::Waveform), // 12-lead ECG
+    ("1.2.840.10008.5.1.4.1.1.9.1.2", SopCategory::Waveform), // General ECG
+    ("1.2.840.10008.5.1.4.1.1.9.4.1", SopCategory::Waveform), // Basic Voice Audio
+    // Encapsulated documents
+    ("1.2.840.10008.5.1.4.1.1.104.1", SopCategory::EncapsulatedDocument), // PDF
+    ("1.2.840.10008.5.1.4.1.1.104.2", SopCategory::EncapsulatedDocument), // CDA
+    // Registrations
+    ("1.2.840.10008.5.1.4.1.1.66.1", SopCategory::Registration), // Spatial Registration
+    ("1.2.840.10008.5.1.4.1.1.66.2", SopCategory::Registration), // Spatial Fiducials
+    ("1.2.840.10008.5.1.4.1.1.66.3", SopCategory::Registration), // Deformable Registration
+];
+
+impl SopCategory {
+    /// Get the category of a SOP Class UID.
+    pub fn from_uid(uid: &str) -> Self {
+        let uid = trim_padding(uid);
+        SOP_CLASSES
+            .iter()
+            .find(|(known, _)| *known == uid)
+            .map_or(SopCategory::Other, |(_, category)| *category)
+    }
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return the category of the SOP Class UID (x0008x0016).
+    pub fn sop_category(&self) -> SopCategory {
+        self.try_get::<String>(Tag::x0008x0016)
+            .map_or(SopCategory::Other, |uid| SopCategory::from_uid(&uid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::text_element;
+    use crate::TransferSyntax;
+
+    fn object_with_sop_class(uid: &'static str) -> DicomObject<'static> {
+        DicomObject::new(
+            vec![text_element(Tag::x0008x0016, uid)],
+            TransferSyntax::little_endian_implicit(),
+        )
+    }
+
+    #[test]
+    fn sop_categories() {
+        let ct = object_with_sop_class("1.2.840.10008.5.1.4.1.1.2\0");
+        assert_eq!(SopCategory::Image, ct.sop_category());
+        let sr = object_with_sop_class("1.2.840.10008.5.1.4.1.1.88.11\0");
+        assert_eq!(SopCategory::StructuredReport, sr.sop_category());
+        let unknown = object_with_sop_class("1.2.3.4");
+        assert_eq!(SopCategory::Other, unknown.sop_category());
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(SopCategory::Other, obj.sop_category());
+    }
+}
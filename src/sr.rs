@@ -0,0 +1,212 @@
+//! Structured reports (SR) store their content as a tree of content items. Each content item is
+//! an item of a Content Sequence (x0040xA730) and can contain a nested Content Sequence.
+//!
+//! Numeric content items (TID 300) store their value in the Measured Value Sequence
+//! (x0040xA300), along with the unit as a coded concept.
+use crate::parser::sq::Item;
+use crate::types::{trim_padding, DataElement};
+use crate::{DicomError, DicomObject, DicomResult, Tag};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use nom::number::Endianness;
+
+/// A coded entry, as found in Code Sequence macros (Concept Name Code Sequence, Measurement Units
+/// Code Sequence...)
+///
+/// The code value is read from the Code Value (x0008x0100), or from the Long Code Value
+/// (x0008x0119) or the URN Code Value (x0008x0120) when the code does not fit in 16 characters.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CodedConcept {
+    pub code_value: String,
+    /// Empty for URN codes as the scheme is part of the URN.
+    pub coding_scheme_designator: String,
+    pub coding_scheme_version: Option<String>,
+    pub code_meaning: String,
+}
+
+impl CodedConcept {
+    /// Read the code from an item of a Code Sequence.
+    pub fn from_item(item: &Item) -> DicomResult<Self> {
+        let read = |tag| item.try_get::<String>(tag).map(|v| trim_padding(&v).to_string());
+
+        let (code_value, coding_scheme_designator) = match read(Tag::x0008x0100) {
+            Ok(code_value) => (code_value, read(Tag::x0008x0102)?),
+            Err(DicomError::NoSuchTag(_)) => match read(Tag::x0008x0119) {
+                Ok(code_value) => (code_value, read(Tag::x0008x0102)?),
+                Err(DicomError::NoSuchTag(_)) => (read(Tag::x0008x0120)?, String::new()),
+                Err(e) => return Err(e),
+            },
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            code_value,
+            coding_scheme_designator,
+            coding_scheme_version: read(Tag::x0008x0103).ok(),
+            code_meaning: read(Tag::x0008x0104)?,
+        })
+    }
+
+    /// Read the first item of the code sequence `tag` in the item if it exists.
+    fn from_sequence(item: &Item, tag: Tag) -> DicomResult<Option<Self>> {
+        item.get_element(tag)
+            .and_then(|el| el.items())
+            .and_then(|items| items.first())
+            .map(CodedConcept::from_item)
+            .transpose()
+    }
+}
+
+/// A numeric measurement of a structured report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub value: f64,
+    pub unit: Option<CodedConcept>,
+    pub concept_name: Option<CodedConcept>,
+}
+
+impl<'buf> DicomObject<'buf> {
+    /// Return all the measurements found in the content tree of the object.
+    pub fn measurements(&self) -> DicomResult<Vec<Measurement>> {
+        let mut measurements = vec![];
+        collect_measurements(&self.elements, &mut measurements)?;
+        Ok(measurements)
+    }
+}
+
+fn collect_measurements(elements: &[DataElement], measurements: &mut Vec<Measurement>) -> DicomResult<()> {
+    for item in elements.iter().filter_map(|el| el.items()).flatten() {
+        if let Some(values) = item.get_element(Tag::x0040xA300).and_then(|el| el.items()) {
+            let concept_name = CodedConcept::from_sequence(item, Tag::x0040xA043)?;
+            for value in values {
+                let numeric: String = value.try_get(Tag::x0040xA30A)?;
+                let numeric = trim_padding(&numeric);
+                measurements.push(Measurement {
+                    value: numeric.parse().map_err(|_| {
+                        DicomError::ParseError(format!("Invalid numeric value = {}", numeric))
+                    })?,
+                    unit: CodedConcept::from_sequence(value, Tag::x0040x08EA)?,
+                    concept_name: concept_name.clone(),
+                });
+            }
+        }
+
+        collect_measurements(&item.elements, measurements)?;
+    }
+
+    Ok(())
+}
+
+/// Read the Referenced Waveform Channels (x0040xA0B0) of a waveform annotation or of a content
+/// item as (multiplex group, channel) pairs. The numbers start at 1.
+///
+/// The attribute is optional: an empty list means that the annotation applies to all the
+/// channels.
+pub fn referenced_waveform_channels(item: &Item) -> DicomResult<Vec<(u16, u16)>> {
+    let data = match item.get_element(Tag::x0040xA0B0) {
+        Some(el) => el.data.as_bytes().ok_or_else(|| {
+            DicomError::ConvertTypeExpectBuf("Referenced Waveform Channels".to_string())
+        })?,
+        None => return Ok(vec![]),
+    };
+    if data.len() % 4 != 0 {
+        return Err(DicomError::ParseError(format!(
+            "Referenced Waveform Channels should be pairs of US but has {} bytes",
+            data.len()
+        )));
+    }
+
+    let read_u16 = match item.transfer_syntax.endianness() {
+        Endianness::Little => LittleEndian::read_u16,
+        Endianness::Big => BigEndian::read_u16,
+    };
+    Ok(data
+        .chunks_exact(4)
+        .map(|pair| (read_u16(&pair[..2]), read_u16(&pair[2..])))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{element, sequence, text_element};
+    use crate::TransferSyntax;
+
+    #[test]
+    fn read_measurement() {
+        let content = sequence(
+            Tag::x0040xA730,
+            vec![vec![
+                text_element(Tag::x0040xA040, "NUM "),
+                sequence(
+                    Tag::x0040xA043,
+                    vec![vec![
+                        text_element(Tag::x0008x0100, "410668003"),
+                        text_element(Tag::x0008x0102, "SCT "),
+                        text_element(Tag::x0008x0104, "Length"),
+                    ]],
+                ),
+                sequence(
+                    Tag::x0040xA300,
+                    vec![vec![
+                        text_element(Tag::x0040xA30A, "12.5"),
+                        sequence(
+                            Tag::x0040x08EA,
+                            vec![vec![
+                                text_element(Tag::x0008x0100, "mm"),
+                                text_element(Tag::x0008x0102, "UCUM"),
+                                text_element(Tag::x0008x0104, "millimeter"),
+                            ]],
+                        ),
+                    ]],
+                ),
+            ]],
+        );
+        let obj = DicomObject::new(vec![content], TransferSyntax::little_endian_explicit());
+
+        let measurements = obj.measurements().unwrap();
+        assert_eq!(1, measurements.len());
+        let measurement = &measurements[0];
+        assert_eq!(12.5, measurement.value);
+
+        let unit = measurement.unit.as_ref().unwrap();
+        assert_eq!("mm", unit.code_value);
+        assert_eq!("UCUM", unit.coding_scheme_designator);
+        assert_eq!("SCT", measurement.concept_name.as_ref().unwrap().coding_scheme_designator);
+    }
+
+    #[test]
+    fn read_long_code_value() {
+        let code = Item {
+            elements: vec![
+                text_element(Tag::x0008x0119, "LONG-CODE-VALUE-0123456789"),
+                text_element(Tag::x0008x0102, "99TEST"),
+                text_element(Tag::x0008x0103, "1.0 "),
+                text_element(Tag::x0008x0104, "Long code"),
+            ],
+            transfer_syntax: TransferSyntax::little_endian_explicit(),
+        };
+
+        let concept = CodedConcept::from_item(&code).unwrap();
+        assert_eq!("LONG-CODE-VALUE-0123456789", concept.code_value);
+        assert_eq!("99TEST", concept.coding_scheme_designator);
+        assert_eq!(Some("1.0".to_string()), concept.coding_scheme_version);
+        assert_eq!("Long code", concept.code_meaning);
+    }
+
+    #[test]
+    fn read_referenced_waveform_channels() {
+        // The values are read with the transfer syntax of the item.
+        let channels: Vec<u8> = [1u16, 2, 1, 3].iter().flat_map(|v| v.to_be_bytes()).collect();
+        let annotation = Item {
+            elements: vec![element(Tag::x0040xA0B0, channels)],
+            transfer_syntax: TransferSyntax::big_endian_explicit(),
+        };
+        assert_eq!(vec![(1, 2), (1, 3)], referenced_waveform_channels(&annotation).unwrap());
+
+        let annotation = Item {
+            elements: vec![],
+            transfer_syntax: TransferSyntax::little_endian_explicit(),
+        };
+        assert!(referenced_waveform_channels(&annotation).unwrap().is_empty());
+    }
+}
@@ -3,10 +3,11 @@
 //!
 //! You can use the macro `tags!` to add a known tag to the crate. If a tag is parsed and is not
 //! defined by the macro, the tag will be `Tag::UNKNOWN(u16, u16)`
+use crate::ValueRepresentation;
 use std::fmt;
 use log::trace;
 macro_rules! tags {
-    ($( ($name:ident, $_0:expr, $_1:expr, $multiplicity:expr, $repr:expr, $kw:expr)),+) => {
+    ($( ($name:ident, $_0:expr, $_1:expr, $vr:expr, $multiplicity:expr, $repr:expr, $kw:expr)),+) => {
 
         #[allow(non_camel_case_types)]
         #[derive(Eq, PartialEq, Copy, Clone, Hash)]
@@ -54,6 +55,24 @@ macro_rules! tags {
                 }
             }
 
+            /// Return the element number for the given tag.
+            pub fn get_element(&self) -> u16 {
+                match *self {
+                    $(Tag::$name => $_1,)+
+                    Tag::UNKNOWN(_, element) => element,
+                }
+            }
+
+            /// Return the VR of the tag from the dictionary. This is the VR to use when the
+            /// transfer syntax has implicit VR. None for unknown tags and for the items and
+            /// delimiters.
+            pub fn vr(&self) -> Option<ValueRepresentation> {
+                match *self {
+                    $(Tag::$name => $vr,)+
+                    Tag::UNKNOWN(_, _) => None,
+                }
+            }
+
             pub fn multiplicity(&self) -> usize {
                 match *self {
                     $(Tag::$name => $multiplicity,)+
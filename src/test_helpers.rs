@@ -0,0 +1,53 @@
+//! Factories of data elements for the unit tests. The binary values are in little endian and the
+//! elements have no VR unless one is given, like in implicit VR.
+use crate::parser::sq::Item;
+use crate::types::{DataElement, Value};
+use crate::{Tag, TransferSyntax, ValueRepresentation};
+
+pub(crate) fn element(tag: Tag, data: Vec<u8>) -> DataElement<'static> {
+    DataElement {
+        tag,
+        vr: None,
+        length: data.len() as u32,
+        data: Value::Owned(data),
+    }
+}
+
+pub(crate) fn vr_element(tag: Tag, vr: ValueRepresentation, data: Vec<u8>) -> DataElement<'static> {
+    DataElement {
+        vr: Some(vr),
+        ..element(tag, data)
+    }
+}
+
+pub(crate) fn text_element(tag: Tag, value: &'static str) -> DataElement<'static> {
+    DataElement {
+        tag,
+        vr: None,
+        length: value.len() as u32,
+        data: Value::Buf(value.as_bytes()),
+    }
+}
+
+pub(crate) fn us_element(tag: Tag, value: u16) -> DataElement<'static> {
+    element(tag, value.to_le_bytes().to_vec())
+}
+
+/// Sequence with undefined length, each item is given by its elements. The items are in implicit
+/// VR little endian.
+pub(crate) fn sequence(tag: Tag, items: Vec<Vec<DataElement<'static>>>) -> DataElement<'static> {
+    DataElement {
+        tag,
+        vr: None,
+        length: u32::MAX,
+        data: Value::Sequence(
+            items
+                .into_iter()
+                .map(|elements| Item {
+                    elements,
+                    transfer_syntax: TransferSyntax::little_endian_implicit(),
+                })
+                .collect(),
+        ),
+    }
+}
@@ -1,15 +1,24 @@
 //! Types specific to Dicom.
 use crate::error::*;
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use chrono::NaiveDate;
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
 use std::fmt::{self, Display};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io::Cursor;
 use nom::number::Endianness;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 
 use crate::{Tag, ValueRepresentation};
+use crate::parser::encapsulated::parse_encapsulated;
 use crate::parser::sq::Item;
+#[cfg(feature = "image")]
 use crate::img::DicomImage;
+use crate::img::{ImageGeometry, PixelGeometry};
+
+/// A DICOM object that does not borrow the parsed buffer, see `DicomObject::into_owned`. It can
+/// be cached or returned by functions that own the bytes.
+pub type OwnedDicomObject = DicomObject<'static>;
 
 /// Represent a DICOM file
 #[derive(Debug)]
@@ -19,7 +28,19 @@ pub struct DicomObject<'buf> {
     /// Transfer syntax extracted from x0002
     pub transfer_syntax: TransferSyntax,
 
+    /// Decoded image, only available with the `image` feature.
+    #[cfg(feature = "image")]
     pub image: Option<DicomImage>,
+
+    /// Value of the pixel data element (x7FE0x0010), if it was found.
+    pub pixel_data: Option<Cow<'buf, [u8]>>,
+
+    /// Bytes of the file meta information (preamble, DICM and group 2) as they were parsed.
+    pub meta: Option<Cow<'buf, [u8]>>,
+
+    /// Number of bytes of the buffer that were parsed. It is smaller than the buffer when there
+    /// is some data after the pixel data. 0 if the object was not parsed from a buffer.
+    pub bytes_consumed: usize,
 }
 
 impl<'buf> DicomObject<'buf> {
@@ -27,22 +48,133 @@ impl<'buf> DicomObject<'buf> {
         Self {
             elements,
             transfer_syntax,
+            #[cfg(feature = "image")]
             image: None,
+            pixel_data: None,
+            meta: None,
+            bytes_consumed: 0,
         }
     }
 
+    /// Copy all the borrowed data so that the object does not depend on the parsed buffer
+    /// anymore.
+    pub fn into_owned(self) -> OwnedDicomObject {
+        DicomObject {
+            elements: self.elements.into_iter().map(DataElement::into_owned).collect(),
+            transfer_syntax: self.transfer_syntax,
+            #[cfg(feature = "image")]
+            image: self.image,
+            pixel_data: self.pixel_data.map(|data| Cow::Owned(data.into_owned())),
+            meta: self.meta.map(|data| Cow::Owned(data.into_owned())),
+            bytes_consumed: self.bytes_consumed,
+        }
+    }
+
+    /// Return the file meta information exactly as it was in the file: the preamble, DICM and the
+    /// group 2. The preamble and DICM are missing when the file has none. None if the object was
+    /// not parsed from a file.
+    pub fn meta_bytes(&self) -> Option<&[u8]> {
+        self.meta.as_deref()
+    }
+
     pub fn append(&mut self, mut elements: Vec<DataElement<'buf>>) {
         self.elements.append(&mut elements);
     }
 
-    pub fn elements(&self) -> &Vec<DataElement> {
+    pub fn elements(&self) -> &Vec<DataElement<'_>> {
         &self.elements
     }
 
-    pub fn get_element(&self, tag: Tag) -> Option<&DataElement> {
+    pub fn get_element(&self, tag: Tag) -> Option<&DataElement<'_>> {
         self.elements.iter().find(|el| el.tag == tag)
     }
 
+    /// Return the element to modify its value in place. A new value must outlive the object, see
+    /// `set_element`.
+    pub fn get_element_mut(&mut self, tag: Tag) -> Option<&mut DataElement<'buf>> {
+        self.elements.iter_mut().find(|el| el.tag == tag)
+    }
+
+    /// Replace the element that has the same tag, or insert it before the first element with a
+    /// greater tag so that the elements stay in ascending order, as they must be written.
+    ///
+    /// The element can borrow its data, which must then outlive the object like the parsed
+    /// buffer. Use `Value::Owned` for new values to avoid this constraint.
+    pub fn set_element(&mut self, el: DataElement<'buf>) {
+        if let Some(existing) = self.get_element_mut(el.tag) {
+            *existing = el;
+            return;
+        }
+        let key = |tag: Tag| (tag.get_group(), tag.get_element());
+        let index = self
+            .elements
+            .iter()
+            .position(|other| key(other.tag) > key(el.tag))
+            .unwrap_or(self.elements.len());
+        self.elements.insert(index, el);
+    }
+
+    /// Return all the elements of a group, in parsed order, including the ones that are not in
+    /// the dictionary (private tags for example).
+    pub fn group(&self, group: u16) -> Vec<&DataElement<'buf>> {
+        self.elements.iter().filter(|el| el.tag.get_group() == group).collect()
+    }
+
+    /// Return a private element from its private creator. The Private Creator (ggggx0010 to
+    /// ggggx00FF) reserves a block of elements: the creator at ggggx00bb owns the elements
+    /// ggggxbb00 to ggggxbbFF. `element_byte` is the last byte of the element number.
+    ///
+    /// None if `group` is not a private (odd) group, or if the creator has no block.
+    pub fn private_element(
+        &self,
+        group: u16,
+        creator: &str,
+        element_byte: u8,
+    ) -> Option<&DataElement<'_>> {
+        if group % 2 == 0 {
+            return None;
+        }
+        let block = self
+            .group(group)
+            .into_iter()
+            .filter(|el| (0x0010..=0x00FF).contains(&el.tag.get_element()))
+            .find(|el| {
+                String::from_element(el, &self.transfer_syntax)
+                    .is_ok_and(|value| trim_padding(&value) == creator)
+            })?
+            .tag
+            .get_element();
+        self.get_element(Tag::from_values(group, block << 8 | element_byte as u16))
+    }
+
+    /// Return all the sequences, depth first, with their number of items. A sequence is
+    /// identified by its path: the tags of the sequences that contain it, then its own tag. A
+    /// nested sequence is listed once per item that contains it.
+    pub fn sequences(&self) -> Vec<(Vec<Tag>, usize)> {
+        fn visit(elements: &[DataElement], path: &mut Vec<Tag>, out: &mut Vec<(Vec<Tag>, usize)>) {
+            for el in elements {
+                if let Some(items) = el.items() {
+                    path.push(el.tag);
+                    out.push((path.clone(), items.len()));
+                    for item in items {
+                        visit(&item.elements, path, out);
+                    }
+                    path.pop();
+                }
+            }
+        }
+
+        let mut sequences = vec![];
+        visit(&self.elements, &mut vec![], &mut sequences);
+        sequences
+    }
+
+    /// Return the position of the element in `elements`, which is the order in which the
+    /// elements were parsed.
+    pub fn element_index(&self, tag: Tag) -> Option<usize> {
+        self.elements.iter().position(|el| el.tag == tag)
+    }
+
     pub fn get<T: FromDicomValue + 'static>(&self, tag: Tag) -> T {
         match self.try_get(tag) {
             Ok(v) => v,
@@ -57,10 +189,455 @@ impl<'buf> DicomObject<'buf> {
 
     pub fn try_get<T: FromDicomValue>(&self, tag: Tag) -> DicomResult<T> {
         match self.get_element(tag) {
-            Some(ref el) => FromDicomValue::from_element(el, &self.transfer_syntax),
+            Some(el) => FromDicomValue::from_element(el, &self.transfer_syntax),
+            None => Err(DicomError::NoSuchTag(tag)),
+        }
+    }
+
+    /// Return the dimensions and the pixel depth of the image.
+    pub fn image_geometry(&self) -> DicomResult<ImageGeometry> {
+        Ok(ImageGeometry {
+            rows: self.try_get(Tag::x0028x0010)?,
+            columns: self.try_get(Tag::x0028x0011)?,
+            bits_allocated: self.try_get(Tag::x0028x0100)?,
+            bits_stored: self.try_get(Tag::x0028x0101)?,
+            samples_per_pixel: self.try_get(Tag::x0028x0002).unwrap_or(1),
+        })
+    }
+
+    /// Return the size in bytes of the uncompressed pixel data of all the frames, as described by
+    /// the image geometry and the number of frames.
+    pub fn expected_pixel_bytes(&self) -> Option<u64> {
+        let geometry = self.image_geometry().ok()?;
+        let bits = geometry.sample_count() as u64
+            * geometry.bits_allocated as u64
+            * self.number_of_frames() as u64;
+        Some(bits.div_ceil(8))
+    }
+
+    /// Return the pixel spacing (x0028x0030) and the slice thickness (x0018x0050). For enhanced
+    /// objects, they are read from the Pixel Measures Sequence (x0028x9110) of the first frame,
+    /// see `functional_group_value`.
+    pub fn pixel_geometry(&self) -> Option<PixelGeometry> {
+        if let Ok(spacing) = self.decimals(Tag::x0028x0030) {
+            return Some(PixelGeometry {
+                pixel_spacing: spacing,
+                slice_thickness: self.decimals::<1>(Tag::x0018x0050).ok().map(|t| t[0]),
+            });
+        }
+
+        let spacing: Vec<f64> = self
+            .functional_group_value(0, Tag::x0028x9110, Tag::x0028x0030)
+            .ok()?;
+        Some(PixelGeometry {
+            pixel_spacing: spacing.try_into().ok()?,
+            slice_thickness: self
+                .functional_group_value(0, Tag::x0028x9110, Tag::x0018x0050)
+                .ok(),
+        })
+    }
+
+    /// Return the Spacing Between Slices (x0018x0088) in mm, read from the Pixel Measures
+    /// Sequence (x0028x9110) of the first frame for enhanced objects.
+    pub fn spacing_between_slices(&self) -> Option<f64> {
+        self.decimals::<1>(Tag::x0018x0088)
+            .map(|s| s[0])
+            .or_else(|_| self.functional_group_value(0, Tag::x0028x9110, Tag::x0018x0088))
+            .ok()
+    }
+
+    /// Read a value of an enhanced object, in the functional group sequence `group_tag` (for
+    /// example the MR Echo Sequence, x0018x9114) of a frame. The Per-frame Functional Groups
+    /// Sequence (x5200x9230) is used first, then the Shared Functional Groups Sequence
+    /// (x5200x9229).
+    pub fn functional_group_value<T: FromDicomValue>(
+        &self,
+        frame: usize,
+        group_tag: Tag,
+        leaf_tag: Tag,
+    ) -> DicomResult<T> {
+        let per_frame = self
+            .get_element(Tag::x5200x9230)
+            .and_then(|el| el.items())
+            .and_then(|items| items.get(frame));
+        let shared = self
+            .get_element(Tag::x5200x9229)
+            .and_then(|el| el.items())
+            .and_then(|items| items.first());
+
+        per_frame
+            .into_iter()
+            .chain(shared)
+            .filter_map(|groups| groups.get_element(group_tag))
+            .filter_map(|el| el.items())
+            .filter_map(|items| items.first())
+            .find(|item| item.get_element(leaf_tag).is_some())
+            .ok_or(DicomError::NoSuchTag(leaf_tag))?
+            .try_get(leaf_tag)
+    }
+
+    /// Return the Stack ID (x0020x9056) and the In-Stack Position Number (x0020x9057) of a frame
+    /// of an enhanced object, from its Frame Content Sequence (x0020x9111). Frames with the same
+    /// stack ID belong to the same stack, ordered by their position which starts at 1.
+    pub fn frame_stack_position(&self, frame: usize) -> Option<(String, i64)> {
+        let stack_id: String = self
+            .functional_group_value(frame, Tag::x0020x9111, Tag::x0020x9056)
+            .ok()?;
+        let position: u32 = self
+            .functional_group_value(frame, Tag::x0020x9111, Tag::x0020x9057)
+            .ok()?;
+        Some((trim_padding(&stack_id).to_string(), position as i64))
+    }
+
+    /// Return the Image Position (Patient) (x0020x0032): the coordinates in mm of the center of
+    /// the first pixel of the image.
+    pub fn image_position(&self) -> DicomResult<[f64; 3]> {
+        self.decimals(Tag::x0020x0032)
+    }
+
+    /// Return the Image Orientation (Patient) (x0020x0037): the direction cosines of the first
+    /// row followed by the ones of the first column.
+    pub fn image_orientation(&self) -> DicomResult<[f64; 6]> {
+        self.decimals(Tag::x0020x0037)
+    }
+
+    /// Return the normal of the image plane, the cross product of the row and column direction
+    /// cosines.
+    pub fn slice_normal(&self) -> Option<[f64; 3]> {
+        let o = self.image_orientation().ok()?;
+        Some([
+            o[1] * o[5] - o[2] * o[4],
+            o[2] * o[3] - o[0] * o[5],
+            o[0] * o[4] - o[1] * o[3],
+        ])
+    }
+
+    /// Return the position of the image along its normal. Sorting the slices of a volume by this
+    /// value stacks them in order, whatever the orientation of the acquisition.
+    pub fn projected_position(&self) -> Option<f64> {
+        let normal = self.slice_normal()?;
+        let position = self.image_position().ok()?;
+        Some(position.iter().zip(normal.iter()).map(|(p, n)| p * n).sum())
+    }
+
+    /// Read a DS value with exactly `N` values.
+    fn decimals<const N: usize>(&self, tag: Tag) -> DicomResult<[f64; N]> {
+        let values: Vec<f64> = self.try_get(tag)?;
+        values.try_into().map_err(|values: Vec<f64>| {
+            DicomError::ParseError(format!(
+                "{:?} should have {} values but got {}",
+                tag,
+                N,
+                values.len()
+            ))
+        })
+    }
+
+    /// Return true if the image has been lossy compressed, either with the transfer syntax of
+    /// the object or before (Lossy Image Compression (x0028x2110) is "01").
+    pub fn is_lossy(&self) -> bool {
+        if self.transfer_syntax.compression_scheme == Some(CompressionScheme::Jpeg2000Lossy) {
+            return true;
+        }
+
+        self.try_get::<String>(Tag::x0028x2110)
+            .is_ok_and(|lossy| trim_padding(&lossy) == "01")
+    }
+
+    /// Return the minimum and maximum values that can be stored in a pixel, according to the
+    /// bits stored (x0028x0101) and the pixel representation (x0028x0103, 1 when signed).
+    pub fn stored_value_range(&self) -> Option<(i64, i64)> {
+        let bits_stored: u16 = self.try_get(Tag::x0028x0101).ok()?;
+        let pixel_representation: u16 = self.try_get(Tag::x0028x0103).unwrap_or(0);
+        if bits_stored == 0 || bits_stored > 32 {
+            return None;
+        }
+
+        if pixel_representation == 1 {
+            let half = 1i64 << (bits_stored - 1);
+            Some((-half, half - 1))
+        } else {
+            Some((0, (1i64 << bits_stored) - 1))
+        }
+    }
+
+    /// Return the instance number (x0020x0013), used to sort the instances of a series.
+    pub fn instance_number(&self) -> Option<i64> {
+        self.try_get::<i32>(Tag::x0020x0013).ok().map(i64::from)
+    }
+
+    /// Return the acquisition number (x0020x0012).
+    pub fn acquisition_number(&self) -> Option<i64> {
+        self.try_get::<i32>(Tag::x0020x0012).ok().map(i64::from)
+    }
+
+    /// Return the Content Date (x0008x0023) and Content Time (x0008x0033) as a timestamp.
+    pub fn content_datetime(&self) -> Option<NaiveDateTime> {
+        self.datetime(Tag::x0008x0023, Tag::x0008x0033)
+    }
+
+    /// Return the Series Date (x0008x0021) and Series Time (x0008x0031) as a timestamp.
+    pub fn series_datetime(&self) -> Option<NaiveDateTime> {
+        self.datetime(Tag::x0008x0021, Tag::x0008x0031)
+    }
+
+    /// Combine a DA and a TM element. Midnight is used when the time is missing.
+    fn datetime(&self, date: Tag, time: Tag) -> Option<NaiveDateTime> {
+        let date: NaiveDate = self.try_get(date).ok()?;
+        let time = match self.get_element(time) {
+            Some(_) => self.try_get(time).ok()?,
+            None => NaiveTime::MIN,
+        };
+        Some(date.and_time(time))
+    }
+
+    /// Change the transfer syntax of the object, so that it is encoded with the new syntax when
+    /// written. The missing VRs are filled from the dictionary (UN for unknown tags) and the binary
+    /// values are swapped when the endianness changes. The transfer syntax UID (x0002x0010) is
+    /// updated.
+    ///
+    /// Only the metadata is transcoded so the compression of the pixel data cannot change.
+    pub fn set_transfer_syntax(&mut self, transfer_syntax: TransferSyntax) -> DicomResult<()> {
+        if self.transfer_syntax.compression_scheme != transfer_syntax.compression_scheme {
+            return Err(DicomError::TransferSyntaxNotSupported(format!(
+                "Cannot transcode the pixel data from {:?} to {:?}",
+                self.transfer_syntax.compression_scheme, transfer_syntax.compression_scheme
+            )));
+        }
+        let uid = transfer_syntax
+            .known()
+            .ok_or_else(|| DicomError::TransferSyntaxNotSupported(format!("{:?}", transfer_syntax)))?
+            .uid();
+
+        let swap = self.transfer_syntax.endianness() != transfer_syntax.endianness();
+        for el in self.elements.iter_mut() {
+            transcode_element(el, swap, transfer_syntax);
+        }
+        if swap && !transfer_syntax.is_encapsulated() {
+            let bits_allocated: u16 = self.try_get(Tag::x0028x0100).unwrap_or(8);
+            if let (16, Some(pixel_data)) = (bits_allocated, self.pixel_data.as_mut()) {
+                swap_bytes(pixel_data.to_mut(), 2);
+            }
+        }
+
+        let mut uid = uid.as_bytes().to_vec();
+        if uid.len() % 2 == 1 {
+            uid.push(0);
+        }
+        let ts_element = DataElement {
+            tag: Tag::x0002x0010,
+            vr: Some(ValueRepresentation::UI),
+            length: uid.len() as u32,
+            data: Value::Owned(uid),
+        };
+        match self.element_index(Tag::x0002x0010) {
+            Some(index) => self.elements[index] = ts_element,
+            None => self.elements.insert(0, ts_element),
+        }
+
+        self.transfer_syntax = transfer_syntax;
+        Ok(())
+    }
+
+    /// Return the elements as strings keyed by "gggg,eeee", for logging or indexing. The values
+    /// are converted with `DataElement::to_display_string` and the pixel data is skipped.
+    pub fn to_btreemap(&self) -> BTreeMap<String, String> {
+        self.elements
+            .iter()
+            .filter(|el| el.tag != Tag::x7FE0x0010)
+            .map(|el| {
+                (
+                    format!("{:04X},{:04X}", el.tag.get_group(), el.tag.get_element()),
+                    el.to_display_string(&self.transfer_syntax),
+                )
+            })
+            .collect()
+    }
+
+    /// Return a flat listing of the elements, without their children.
+    pub fn summary(&self) -> Vec<ElementSummary> {
+        self.elements
+            .iter()
+            .map(|el| {
+                let mut preview = el.to_display_string(&self.transfer_syntax);
+                if let Some((index, _)) = preview.char_indices().nth(PREVIEW_LEN) {
+                    preview.truncate(index);
+                    preview.push_str("...");
+                }
+                ElementSummary {
+                    tag: el.tag,
+                    vr: el.vr.clone().or_else(|| el.tag.vr()),
+                    length: el.length,
+                    preview,
+                }
+            })
+            .collect()
+    }
+
+    /// Return the Frame of Reference UID (x0020x0052). Images that share it are in the same
+    /// patient coordinate system.
+    pub fn frame_of_reference_uid(&self) -> Option<String> {
+        self.try_get::<String>(Tag::x0020x0052)
+            .ok()
+            .map(|v| trim_padding(&v).to_string())
+    }
+
+    /// Return the Pixel Data Provider URL (x0028x7FE0). When it is present, the pixels are not
+    /// in the object but have to be retrieved from this URL, for example with JPIP.
+    pub fn pixel_data_url(&self) -> Option<String> {
+        self.try_get::<String>(Tag::x0028x7FE0)
+            .ok()
+            .map(|v| trim_padding(&v).to_string())
+    }
+
+    /// Return the Study Instance UID (x0020x000D) and the Series Instance UID (x0020x000E), to
+    /// group the instances by series.
+    pub fn series_key(&self) -> Option<(String, String)> {
+        let uid = |tag| {
+            self.try_get::<String>(tag)
+                .ok()
+                .map(|v| trim_padding(&v).to_string())
+        };
+        Some((uid(Tag::x0020x000D)?, uid(Tag::x0020x000E)?))
+    }
+
+    /// Return the Concatenation UID (x0020x9161). Large enhanced objects can be split in several
+    /// instances that share this UID.
+    pub fn concatenation_uid(&self) -> Option<String> {
+        self.try_get::<String>(Tag::x0020x9161)
+            .ok()
+            .map(|v| trim_padding(&v).to_string())
+    }
+
+    /// Return the In-concatenation Number (x0020x9162), the position of this instance in its
+    /// concatenation, starting at 1.
+    pub fn in_concatenation_number(&self) -> Option<u16> {
+        self.try_get(Tag::x0020x9162).ok()
+    }
+
+    /// Return the Concatenation Frame Offset Number (x0020x9228), the number of frames of the
+    /// concatenation that are before the first frame of this instance.
+    pub fn concatenation_frame_offset(&self) -> Option<u32> {
+        self.try_get(Tag::x0020x9228).ok()
+    }
+
+    /// Return the number of frames (x0028x0008). When it is missing from an encapsulated object,
+    /// it is the number of entries of the Basic Offset Table. Default to 1.
+    pub fn number_of_frames(&self) -> u32 {
+        if let Ok(frames) = self.try_get::<i32>(Tag::x0028x0008) {
+            return frames.max(1) as u32;
+        }
+
+        if self.transfer_syntax.is_encapsulated() {
+            let offset_table_len = self
+                .pixel_data
+                .as_ref()
+                .and_then(|data| parse_encapsulated(data).ok())
+                .map_or(0, |(_, pixel_data)| pixel_data.offset_table.len());
+            if offset_table_len > 0 {
+                return offset_table_len as u32;
+            }
+        }
+
+        1
+    }
+
+    /// Return the character set used by the text values (x0008x0005). Only the first value is
+    /// used and unsupported character sets fall back to the default repertoire.
+    pub fn character_set(&self) -> CharacterSet {
+        self.try_get::<String>(Tag::x0008x0005)
+            .ok()
+            .and_then(|v| {
+                v.split('\\')
+                    .next()
+                    .and_then(|term| CharacterSet::from_term(trim_padding(term)))
+            })
+            .unwrap_or(CharacterSet::Default)
+    }
+
+    /// Return a text value decoded with the character set of the object, without its padding.
+    pub fn text(&self, tag: Tag) -> DicomResult<String> {
+        match self.get_element(tag).map(|el| el.data.as_bytes()) {
+            Some(Some(data)) => self.character_set().decode(data),
+            Some(None) => Err(DicomError::ConvertTypeExpectBuf("String".to_string())),
             None => Err(DicomError::NoSuchTag(tag)),
         }
     }
+
+    /// Return the Derivation Description (x0008x2111), the free text explaining how the image
+    /// was derived.
+    pub fn derivation_description(&self) -> Option<String> {
+        self.text(Tag::x0008x2111)
+            .ok()
+            .map(|text| trim_trailing_padding(&text).to_string())
+    }
+
+    /// Return the Image Comments (x0020x4000).
+    pub fn image_comments(&self) -> Option<String> {
+        self.text(Tag::x0020x4000)
+            .ok()
+            .map(|text| trim_trailing_padding(&text).to_string())
+    }
+
+    /// Return the age of the patient (x0010x1010). When the tag is missing, the age is computed
+    /// from the patient birth date (x0010x0030) and the study date (x0008x0020).
+    pub fn patient_age(&self) -> Option<Age> {
+        if let Ok(age) = self.try_get::<Age>(Tag::x0010x1010) {
+            return Some(age);
+        }
+
+        let birth_date: NaiveDate = self.try_get(Tag::x0010x0030).ok()?;
+        let study_date: NaiveDate = self.try_get(Tag::x0008x0020).ok()?;
+        Age::between(birth_date, study_date)
+    }
+
+    /// Return the sex of the patient (x0010x0040).
+    pub fn patient_sex(&self) -> Option<Sex> {
+        let sex: String = self.try_get(Tag::x0010x0040).ok()?;
+        match trim_padding(&sex) {
+            "M" => Some(Sex::Male),
+            "F" => Some(Sex::Female),
+            "O" => Some(Sex::Other),
+            _ => None,
+        }
+    }
+
+    /// Return the Image Type (x0008x0008). It has at least two values, otherwise `None` is
+    /// returned.
+    pub fn image_type(&self) -> Option<ImageType> {
+        let mut values: Vec<String> = self.try_get(Tag::x0008x0008).ok()?;
+        if values.len() < 2 {
+            return None;
+        }
+        let other = values.split_off(2);
+        let patient_examination = values.pop()?;
+        let pixel_data = values.pop()?;
+        Some(ImageType { pixel_data, patient_examination, other })
+    }
+
+    /// Return the weight of the patient in kg (x0010x1030).
+    pub fn patient_weight(&self) -> Option<f64> {
+        self.decimals::<1>(Tag::x0010x1030).ok().map(|w| w[0])
+    }
+
+    /// Return the size of the patient in m (x0010x1020).
+    pub fn patient_size(&self) -> Option<f64> {
+        self.decimals::<1>(Tag::x0010x1020).ok().map(|s| s[0])
+    }
+}
+
+/// Maximum number of characters of `ElementSummary::preview`.
+const PREVIEW_LEN: usize = 64;
+
+/// Description of an element returned by `DicomObject::summary`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ElementSummary {
+    pub tag: Tag,
+    /// VR of the element, from the dictionary when it is implicit.
+    pub vr: Option<ValueRepresentation>,
+    pub length: u32,
+    /// Start of the value as text, or in hexadecimal for binary values.
+    pub preview: String,
 }
 
 /// Data elements are the basic unit of a DICOM object.
@@ -71,7 +648,7 @@ impl<'buf> DicomObject<'buf> {
 /// - a buffer that represents something. When value representation is known, the library will be
 ///   able to parse automatically the value to the correct type. Otherwise, it has to be known by
 ///   the user.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataElement<'buf> {
     pub tag: Tag,
     pub vr: Option<ValueRepresentation>,
@@ -79,12 +656,100 @@ pub struct DataElement<'buf> {
     pub data: Value<'buf>,
 }
 
-#[derive(Debug)]
+impl<'buf> DataElement<'buf> {
+    /// Return the items if the element is a sequence.
+    pub fn items(&self) -> Option<&[Item<'buf>]> {
+        match self.data {
+            Value::Sequence(ref items) => Some(items),
+            Value::Buf(_) | Value::Owned(_) => None,
+        }
+    }
+
+    /// Best-effort conversion of the value to a string, for logging or exporting. Binary values
+    /// are decoded according to the VR (explicit or from the dictionary) when it is known and
+    /// written in hexadecimal otherwise. Multiple values are separated by a backslash, as in DICOM.
+    pub fn to_display_string(&self, transfer_syntax: &TransferSyntax) -> String {
+        let data = match self.data.as_bytes() {
+            Some(data) => data,
+            None => return format!("[{} items]", self.items().map_or(0, |items| items.len())),
+        };
+
+        let little_endian = transfer_syntax.endianness() == Endianness::Little;
+        let join = |values: Vec<String>| values.join("\\");
+        let vr = self.vr.clone().or_else(|| self.tag.vr());
+        match vr {
+            Some(ValueRepresentation::US) => join(
+                data.chunks_exact(2)
+                    .map(|c| if little_endian { LittleEndian::read_u16(c) } else { BigEndian::read_u16(c) })
+                    .map(|v| v.to_string())
+                    .collect(),
+            ),
+            Some(ValueRepresentation::UL) => join(
+                data.chunks_exact(4)
+                    .map(|c| if little_endian { LittleEndian::read_u32(c) } else { BigEndian::read_u32(c) })
+                    .map(|v| v.to_string())
+                    .collect(),
+            ),
+            Some(ValueRepresentation::SL) => join(
+                data.chunks_exact(4)
+                    .map(|c| if little_endian { LittleEndian::read_i32(c) } else { BigEndian::read_i32(c) })
+                    .map(|v| v.to_string())
+                    .collect(),
+            ),
+            Some(ref vr) if is_binary_vr(vr) => to_hex(data),
+            _ => match std::str::from_utf8(data) {
+                Ok(text) if text.chars().all(|c| !c.is_control() || c.is_whitespace() || c == '\u{0}') => {
+                    match vr {
+                        Some(ValueRepresentation::ST)
+                        | Some(ValueRepresentation::LT)
+                        | Some(ValueRepresentation::UT) => trim_trailing_padding(text).to_string(),
+                        _ => trim_padding(text).to_string(),
+                    }
+                }
+                _ => to_hex(data),
+            },
+        }
+    }
+
+    /// Copy the data of the element if it is borrowed.
+    pub fn into_owned(self) -> DataElement<'static> {
+        DataElement {
+            tag: self.tag,
+            vr: self.vr,
+            length: self.length,
+            data: self.data.into_owned(),
+        }
+    }
+}
+
+/// Value of a data element. When parsing, the value borrows the parsed buffer. `Owned` is used
+/// when the value needs to outlive the buffer.
+#[derive(Debug, Clone)]
 pub enum Value<'a> {
     Buf(&'a [u8]),
+    Owned(Vec<u8>),
     Sequence(Vec<Item<'a>>)
 }
 
+impl<'a> Value<'a> {
+    /// Return the bytes of the value, or None if the value is a sequence.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match *self {
+            Value::Buf(data) => Some(data),
+            Value::Owned(ref data) => Some(data),
+            Value::Sequence(_) => None,
+        }
+    }
+
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::Buf(data) => Value::Owned(data.to_vec()),
+            Value::Owned(data) => Value::Owned(data),
+            Value::Sequence(items) => Value::Sequence(items.into_iter().map(Item::into_owned).collect()),
+        }
+    }
+}
+
 /// Transfer syntax defines the endianness and the presence of value representation.
 /// It is necessary during parsing. The transfer syntax is defined in the tag (0x0002,0x010) which
 /// is at the beginning of the file
@@ -128,6 +793,11 @@ impl TransferSyntax {
         }
     }
 
+    /// Same transfer syntax with another endianness.
+    pub(crate) fn with_endianness(self, endianness: Endianness) -> Self {
+        Self { endianness, ..self }
+    }
+
     /// Return the endianness in which the dicom data was encoded.
     pub fn endianness(&self) -> Endianness {
         self.endianness
@@ -137,13 +807,67 @@ impl TransferSyntax {
     pub fn is_vr_explicit(&self) -> bool {
         self.is_vr_explicit
     }
+
+    /// Return true if the pixel data is encapsulated (compressed).
+    pub fn is_encapsulated(&self) -> bool {
+        self.compression_scheme.is_some()
+    }
+
+    /// Return the transfer syntax as one of the syntaxes supported by the library, which is
+    /// easier to match.
+    pub fn known(&self) -> Option<KnownTransferSyntax> {
+        match (self.endianness, self.is_vr_explicit, self.compression_scheme) {
+            (Endianness::Little, false, None) => Some(KnownTransferSyntax::ImplicitLE),
+            (Endianness::Little, true, None) => Some(KnownTransferSyntax::ExplicitLE),
+            (Endianness::Big, true, None) => Some(KnownTransferSyntax::ExplicitBE),
+            (Endianness::Little, true, Some(CompressionScheme::Jpeg2000Lossless)) => {
+                Some(KnownTransferSyntax::Jpeg2000Lossless)
+            }
+            (Endianness::Little, true, Some(CompressionScheme::Jpeg2000Lossy)) => {
+                Some(KnownTransferSyntax::Jpeg2000Lossy)
+            }
+            (Endianness::Little, true, Some(CompressionScheme::Rle)) => Some(KnownTransferSyntax::Rle),
+            _ => None,
+        }
+    }
+}
+
+/// Transfer syntaxes supported by the library.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum KnownTransferSyntax {
+    /// 1.2.840.10008.1.2
+    ImplicitLE,
+    /// 1.2.840.10008.1.2.1
+    ExplicitLE,
+    /// 1.2.840.10008.1.2.2
+    ExplicitBE,
+    /// 1.2.840.10008.1.2.4.90
+    Jpeg2000Lossless,
+    /// 1.2.840.10008.1.2.4.91
+    Jpeg2000Lossy,
+    /// 1.2.840.10008.1.2.5
+    Rle,
+}
+
+impl KnownTransferSyntax {
+    /// Return the UID of the transfer syntax, without padding.
+    pub fn uid(&self) -> &'static str {
+        match *self {
+            KnownTransferSyntax::ImplicitLE => "1.2.840.10008.1.2",
+            KnownTransferSyntax::ExplicitLE => "1.2.840.10008.1.2.1",
+            KnownTransferSyntax::ExplicitBE => "1.2.840.10008.1.2.2",
+            KnownTransferSyntax::Jpeg2000Lossless => "1.2.840.10008.1.2.4.90",
+            KnownTransferSyntax::Jpeg2000Lossy => "1.2.840.10008.1.2.4.91",
+            KnownTransferSyntax::Rle => "1.2.840.10008.1.2.5",
+        }
+    }
 }
 
 impl TryFrom<&Value<'_>> for TransferSyntax {
     type Error = DicomError;
 
     fn try_from(v: &Value) -> Result<Self, Self::Error> {
-        if let Value::Buf(bytes) = v {
+        if let Some(bytes) = v.as_bytes() {
             let value = std::str::from_utf8(bytes)?;
             // If a Value Field containing one or more UIDs is an odd number of bytes in length, the Value Field shall be padded with a single trailing NULL (00H) character to ensure that the Value Field is an even number of bytes in length. See Section 9 and Annex B for a complete specification and examples
             // No comment
@@ -151,9 +875,18 @@ impl TryFrom<&Value<'_>> for TransferSyntax {
                 "1.2.840.10008.1.2.2\u{0}" => Ok(TransferSyntax::big_endian_explicit()),
                 "1.2.840.10008.1.2.1\u{0}" => Ok(TransferSyntax::little_endian_explicit()),
                 "1.2.840.10008.1.2\u{0}" => Ok(TransferSyntax::little_endian_implicit()),
+                // JPIP Referenced: the data set is in explicit VR little endian and the pixels
+                // are provided by the Pixel Data Provider URL (x0028x7FE0).
+                "1.2.840.10008.1.2.4.94" => Ok(TransferSyntax::little_endian_explicit()),
+                "1.2.840.10008.1.2.5\u{0}" => Ok(TransferSyntax::with_compression_scheme(
+                    CompressionScheme::Rle,
+                )),
                 "1.2.840.10008.1.2.4.90" => Ok(TransferSyntax::with_compression_scheme(
                     CompressionScheme::Jpeg2000Lossless,
                 )),
+                "1.2.840.10008.1.2.4.91" => Ok(TransferSyntax::with_compression_scheme(
+                    CompressionScheme::Jpeg2000Lossy,
+                )),
                 _ => Err(DicomError::TransferSyntaxNotSupported(String::from(value))),
             }
         } else {
@@ -162,17 +895,53 @@ impl TryFrom<&Value<'_>> for TransferSyntax {
     }
 }
 
+/// Character set used to encode the text values, defined by the Specific Character Set
+/// (x0008x0005).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CharacterSet {
+    /// ISO_IR 6, the default repertoire (ASCII).
+    Default,
+    /// ISO_IR 100, Latin alphabet No. 1.
+    Latin1,
+    /// ISO_IR 192, UTF-8.
+    Utf8,
+}
+
+impl CharacterSet {
+    /// Get the character set from its defined term. Return None if it is not supported.
+    pub fn from_term(term: &str) -> Option<Self> {
+        match term {
+            "" | "ISO_IR 6" | "ISO 2022 IR 6" => Some(CharacterSet::Default),
+            "ISO_IR 100" | "ISO 2022 IR 100" => Some(CharacterSet::Latin1),
+            "ISO_IR 192" => Some(CharacterSet::Utf8),
+            _ => None,
+        }
+    }
+
+    /// Decode the bytes of a text value. The padding is removed before decoding as the value
+    /// length is a number of bytes, not of characters.
+    pub fn decode(&self, data: &[u8]) -> DicomResult<String> {
+        let data = trim_padding_bytes(data);
+        match *self {
+            CharacterSet::Latin1 => Ok(data.iter().map(|&b| b as char).collect()),
+            CharacterSet::Default | CharacterSet::Utf8 => Ok(std::str::from_utf8(data)?.to_string()),
+        }
+    }
+}
+
 /// Sometime DCM files contain the image as JPG...
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum CompressionScheme {
     Jpeg2000Lossless,
+    Jpeg2000Lossy,
+    Rle,
 }
 
 /// Trait to convert a series of bytes to the correct type.
 ///
 /// ```rust
 /// use dicom::types::FromDicomValue;
-/// use dicom::element::{Value, DataElement};
+/// use dicom::types::{Value, DataElement};
 /// use dicom::{Tag, TransferSyntax};
 /// let content = vec![0x00, 0x01];
 /// let element = DataElement {
@@ -194,7 +963,7 @@ impl FromDicomValue for u16 {
         el: &DataElement,
         transfer_syntax: &TransferSyntax,
     ) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
+        if let Some(data) = el.data.as_bytes() {
             let mut rdr = Cursor::new(data);
             let repr = if let Endianness::Little = transfer_syntax.endianness() {
                 rdr.read_u16::<LittleEndian>()?
@@ -208,6 +977,52 @@ impl FromDicomValue for u16 {
     }
 }
 
+/// Implementation of the trait for the VR SS (signed short).
+impl FromDicomValue for i16 {
+    fn from_element(
+        el: &DataElement,
+        transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            let mut rdr = Cursor::new(data);
+            let repr = if let Endianness::Little = transfer_syntax.endianness() {
+                rdr.read_i16::<LittleEndian>()?
+            } else {
+                rdr.read_i16::<BigEndian>()?
+            };
+            Ok(repr)
+        } else {
+            Err(DicomError::ConvertTypeExpectBuf("i16".to_string()))
+        }
+    }
+}
+
+/// Implementation of the trait for the VR UL (unsigned long). When the VR (explicit or from the
+/// dictionary) is IS, the integer string is parsed instead, so that counts such as the Number of
+/// Frames (x0028x0008) can be read as u32.
+impl FromDicomValue for u32 {
+    fn from_element(
+        el: &DataElement,
+        transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            if el.vr.clone().or_else(|| el.tag.vr()) == Some(ValueRepresentation::IS) {
+                let v = remove_whitespace(std::str::from_utf8(data)?);
+                return v.parse().map_err(|_| DicomError::ParseIS(el.tag, v));
+            }
+            let mut rdr = Cursor::new(data);
+            let repr = if let Endianness::Little = transfer_syntax.endianness() {
+                rdr.read_u32::<LittleEndian>()?
+            } else {
+                rdr.read_u32::<BigEndian>()?
+            };
+            Ok(repr)
+        } else {
+            Err(DicomError::ConvertTypeExpectBuf("u32".to_string()))
+        }
+    }
+}
+
 /// Implementation of the trait for i32. It corresponds to the VR IS (integer string)
 /// A string of characters representing an Integer in base-10 (decimal), shall contain only
 /// the characters 0 - 9, with an optional leading "+" or "-".
@@ -216,43 +1031,297 @@ impl FromDicomValue for u16 {
 /// The integer, n, represented shall be in the range:
 ///
 /// -231<= n <= (231-1).
+///
+/// When the VR (explicit or from the dictionary) is SL (signed long), the value is read as a
+/// 4 bytes signed integer with the endianness of the transfer syntax.
 impl FromDicomValue for i32 {
-    fn from_element(el: &DataElement, _transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
+    fn from_element(el: &DataElement, transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            if el.vr.clone().or_else(|| el.tag.vr()) == Some(ValueRepresentation::SL) {
+                let mut rdr = Cursor::new(data);
+                let repr = if let Endianness::Little = transfer_syntax.endianness() {
+                    rdr.read_i32::<LittleEndian>()?
+                } else {
+                    rdr.read_i32::<BigEndian>()?
+                };
+                return Ok(repr);
+            }
             let v = remove_whitespace(std::str::from_utf8(data)?);
-            let is: i32 = v.parse()?;
-            Ok(is)
+            v.parse().map_err(|_| DicomError::ParseIS(el.tag, v))
         } else {
             Err(DicomError::ConvertTypeExpectBuf("i32".to_string()))
         }
     }
 }
 
-fn remove_whitespace(s: &str) -> String {
-    s.chars().filter(|c| !c.is_whitespace()).collect()
+/// Implementation of the trait for a multi-valued IS (integer string). The values are separated
+/// by a backslash and may be padded with spaces. An empty value has no integers.
+impl FromDicomValue for Vec<i32> {
+    fn from_element(el: &DataElement, _transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("Vec<i32>".to_string()))?;
+        let v = trim_padding(std::str::from_utf8(data)?);
+        if v.is_empty() {
+            return Ok(vec![]);
+        }
+        v.split('\\')
+            .map(|value| {
+                let value = value.trim();
+                value
+                    .parse()
+                    .map_err(|_| DicomError::ParseIS(el.tag, value.to_string()))
+            })
+            .collect()
+    }
 }
 
-impl FromDicomValue for String {
-    fn from_element(
-        el: &DataElement,
-        _transfer_syntax: &TransferSyntax,
-    ) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
-            let v = std::str::from_utf8(data)?;
-            Ok(v.to_string())
+/// Implementation of the trait for the VR DS (decimal string), also used for IS. The values are
+/// separated by a backslash and may be padded with leading and trailing spaces. An empty value
+/// has no decimals.
+///
+/// When the VR (explicit or from the dictionary) is OD or FD, the value is read as an array of
+/// doubles with the endianness of the transfer syntax.
+impl FromDicomValue for Vec<f64> {
+    fn from_element(el: &DataElement, transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            if let Some(ValueRepresentation::OD) | Some(ValueRepresentation::FD) =
+                el.vr.clone().or_else(|| el.tag.vr())
+            {
+                if data.len() % 8 != 0 {
+                    return Err(DicomError::ParseError(format!(
+                        "Length of {:?} should be a multiple of 8 but got {}",
+                        el.tag,
+                        data.len()
+                    )));
+                }
+                return Ok(data
+                    .chunks_exact(8)
+                    .map(|c| match transfer_syntax.endianness() {
+                        Endianness::Little => LittleEndian::read_f64(c),
+                        Endianness::Big => BigEndian::read_f64(c),
+                    })
+                    .collect());
+            }
+
+            let v = trim_padding(std::str::from_utf8(data)?);
+            if v.is_empty() {
+                return Ok(vec![]);
+            }
+            v.split('\\')
+                .map(|value| {
+                    let value = value.trim();
+                    value
+                        .parse()
+                        .map_err(|_| DicomError::ParseDS(el.tag, value.to_string()))
+                })
+                .collect()
         } else {
-            Err(DicomError::ConvertTypeExpectBuf("String".to_string()))
+            Err(DicomError::ConvertTypeExpectBuf("Vec<f64>".to_string()))
         }
     }
 }
 
-/// The same DICOM type :) When the VR is known, this will give the correct type.
-#[derive(Debug)]
-pub enum DicomType {
-    Str(Vec<String>),
-    UnsignedInt(Vec<u16>),
-    Date(Vec<NaiveDate>),
-    PersonName(Vec<String>),
+/// Implementation of the trait for the VR FL (single float), read as IEEE-754 with the
+/// endianness of the transfer syntax.
+impl FromDicomValue for f32 {
+    fn from_element(el: &DataElement, transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            let mut rdr = Cursor::new(data);
+            let repr = if let Endianness::Little = transfer_syntax.endianness() {
+                rdr.read_f32::<LittleEndian>()?
+            } else {
+                rdr.read_f32::<BigEndian>()?
+            };
+            Ok(repr)
+        } else {
+            Err(DicomError::ConvertTypeExpectBuf("f32".to_string()))
+        }
+    }
+}
+
+/// Implementation of the trait for a single DS (decimal string), such as the Window Center
+/// (x0028x1050). Only the first value is read when the element is multi-valued. OD and FD are
+/// read as binary doubles, like for `Vec<f64>`.
+impl FromDicomValue for f64 {
+    fn from_element(el: &DataElement, transfer_syntax: &TransferSyntax) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("f64".to_string()))?;
+        if let Some(ValueRepresentation::OD) | Some(ValueRepresentation::FD) =
+            el.vr.clone().or_else(|| el.tag.vr())
+        {
+            let values: Vec<f64> = FromDicomValue::from_element(el, transfer_syntax)?;
+            return values
+                .first()
+                .copied()
+                .ok_or_else(|| DicomError::ParseDS(el.tag, String::new()));
+        }
+
+        let v = std::str::from_utf8(data)?;
+        let value = trim_padding(v).split('\\').next().unwrap_or_default().trim();
+        value
+            .parse()
+            .map_err(|_| DicomError::ParseDS(el.tag, value.to_string()))
+    }
+}
+
+fn remove_whitespace(s: &str) -> String {
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Fill the VR from the dictionary and swap the bytes of the binary values if needed. The element
+/// will be written with `transfer_syntax`. The items of a sequence are transcoded to the transfer
+/// syntax the encoder writes them with, which is not the one of the object for a UN sequence.
+fn transcode_element(el: &mut DataElement, swap: bool, transfer_syntax: TransferSyntax) {
+    if el.vr.is_none() {
+        el.vr = Some(el.tag.vr().unwrap_or(ValueRepresentation::UN));
+    }
+
+    match el.data {
+        Value::Sequence(ref mut items) => {
+            let vr = el.vr.as_ref().expect("The VR is filled above");
+            let items_syntax = crate::parser::encode::items_transfer_syntax(vr, transfer_syntax);
+            for item in items.iter_mut() {
+                let swap = item.transfer_syntax.endianness() != items_syntax.endianness();
+                for el in item.elements.iter_mut() {
+                    transcode_element(el, swap, items_syntax);
+                }
+                item.transfer_syntax = items_syntax;
+            }
+        }
+        Value::Buf(data) if swap => {
+            if let Some(width) = el.vr.as_ref().and_then(binary_width) {
+                let mut data = data.to_vec();
+                swap_bytes(&mut data, width);
+                el.data = Value::Owned(data);
+            }
+        }
+        Value::Owned(ref mut data) if swap => {
+            if let Some(width) = el.vr.as_ref().and_then(binary_width) {
+                swap_bytes(data, width);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Size of the numbers of the binary VRs, which bytes have to be swapped to change the
+/// endianness.
+fn binary_width(vr: &ValueRepresentation) -> Option<usize> {
+    match vr {
+        ValueRepresentation::US
+        | ValueRepresentation::SS
+        | ValueRepresentation::OW
+        | ValueRepresentation::AT => Some(2),
+        ValueRepresentation::UL
+        | ValueRepresentation::SL
+        | ValueRepresentation::FL
+        | ValueRepresentation::OF
+        | ValueRepresentation::OL => Some(4),
+        ValueRepresentation::FD
+        | ValueRepresentation::OD
+        | ValueRepresentation::SV
+        | ValueRepresentation::UV
+        | ValueRepresentation::OV => Some(8),
+        _ => None,
+    }
+}
+
+fn swap_bytes(data: &mut [u8], width: usize) {
+    for number in data.chunks_exact_mut(width) {
+        number.reverse();
+    }
+}
+
+/// VR which values are not text.
+fn is_binary_vr(vr: &ValueRepresentation) -> bool {
+    match vr {
+        ValueRepresentation::UC | ValueRepresentation::UR | ValueRepresentation::UT => false,
+        ValueRepresentation::SQ => false,
+        ValueRepresentation::SS
+        | ValueRepresentation::AT
+        | ValueRepresentation::FL
+        | ValueRepresentation::FD => true,
+        vr => vr.has_special_length(),
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Remove the trailing padding (spaces or null characters) of a value. This works on bytes so that
+/// it can be done before decoding the text.
+pub(crate) fn trim_padding_bytes(data: &[u8]) -> &[u8] {
+    let end = data
+        .iter()
+        .rposition(|&b| b != b' ' && b != 0)
+        .map_or(0, |i| i + 1);
+    &data[..end]
+}
+
+/// Remove the padding of a string value. Depending on the VR, values are padded with spaces or
+/// with a null character to get an even length.
+pub(crate) fn trim_padding(s: &str) -> &str {
+    s.trim_matches(|c: char| c.is_whitespace() || c == '\u{0}')
+}
+
+/// Remove only the trailing padding of a string value. The leading spaces are significant in the
+/// free text VRs (ST, LT, UT).
+pub(crate) fn trim_trailing_padding(s: &str) -> &str {
+    s.trim_end_matches(|c: char| c.is_whitespace() || c == '\u{0}')
+}
+
+impl FromDicomValue for String {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            let v = std::str::from_utf8(data)?;
+            Ok(v.to_string())
+        } else {
+            Err(DicomError::ConvertTypeExpectBuf("String".to_string()))
+        }
+    }
+}
+
+/// Multi-valued text, the values are separated by a backslash (UC, CS, LO...). The text VRs
+/// (ST, LT, UT) are single-valued as a backslash is a valid character for them. The VR comes
+/// from the dictionary when it is implicit.
+///
+/// The standard has no escaping for the backslash: it is not allowed in the values of the other
+/// VRs (PS3.5 6.2), so it is always a delimiter. In particular, two consecutive backslashes are
+/// an empty value, not an escaped backslash.
+impl FromDicomValue for Vec<String> {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        if let Some(data) = el.data.as_bytes() {
+            let v = std::str::from_utf8(trim_padding_bytes(data))?;
+            match el.vr.clone().or_else(|| el.tag.vr()) {
+                Some(ValueRepresentation::ST)
+                | Some(ValueRepresentation::LT)
+                | Some(ValueRepresentation::UT) => Ok(vec![v.to_string()]),
+                _ => Ok(v.split('\\').map(|value| trim_padding(value).to_string()).collect()),
+            }
+        } else {
+            Err(DicomError::ConvertTypeExpectBuf("Vec<String>".to_string()))
+        }
+    }
+}
+
+/// The same DICOM type :) When the VR is known, this will give the correct type.
+#[derive(Debug)]
+pub enum DicomType {
+    Str(Vec<String>),
+    UnsignedInt(Vec<u16>),
+    Date(Vec<NaiveDate>),
+    PersonName(Vec<String>),
     Age(Vec<Age>),
     SignedLong(Vec<i32>),
 }
@@ -320,6 +1389,34 @@ impl Age {
 
         Ok(Age { age, format })
     }
+
+    /// Compute the age between two dates, using the biggest unit that is not zero. Will return
+    /// None if `to` is before `from` or if the age cannot fit in the Age String.
+    pub fn between(from: NaiveDate, to: NaiveDate) -> Option<Age> {
+        if to < from {
+            return None;
+        }
+
+        let mut months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+        if to.day() < from.day() {
+            months -= 1;
+        }
+
+        let (age, format) = if months >= 12 {
+            (months / 12, AgeFormat::Year)
+        } else if months > 0 {
+            (months, AgeFormat::Month)
+        } else {
+            let days = (to - from).num_days() as i32;
+            if days >= 7 {
+                (days / 7, AgeFormat::Week)
+            } else {
+                (days, AgeFormat::Day)
+            }
+        };
+
+        u8::try_from(age).ok().map(|age| Age { age, format })
+    }
 }
 
 impl FromDicomValue for Age {
@@ -327,7 +1424,7 @@ impl FromDicomValue for Age {
         el: &DataElement,
         _transfer_syntax: &TransferSyntax,
     ) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
+        if let Some(data) = el.data.as_bytes() {
             let repr = std::str::from_utf8(data)?;
             let v = Age::parse_from_str(repr)?;
             Ok(v)
@@ -337,12 +1434,35 @@ impl FromDicomValue for Age {
     }
 }
 
+/// AS should be single-valued but malformed files can have several ages separated by a
+/// backslash. An error tells which component cannot be parsed.
+impl FromDicomValue for Vec<Age> {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("Vec<Age>".to_string()))?;
+        trim_padding(std::str::from_utf8(data)?)
+            .split('\\')
+            .enumerate()
+            .map(|(i, repr)| {
+                Age::parse_from_str(trim_padding(repr)).map_err(|e| {
+                    DicomError::ParseAS(format!("Component {} ({:?}): {}", i, repr, e))
+                })
+            })
+            .collect()
+    }
+}
+
 impl FromDicomValue for NaiveDate {
     fn from_element(
         el: &DataElement,
         _transfer_syntax: &TransferSyntax,
     ) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
+        if let Some(data) = el.data.as_bytes() {
             let repr = std::str::from_utf8(data)?;
             let dt = NaiveDate::parse_from_str(repr, "%Y%m%d")?;
             Ok(dt)
@@ -352,20 +1472,135 @@ impl FromDicomValue for NaiveDate {
     }
 }
 
+/// DA can have several dates separated by a backslash, in order. An empty value has no dates.
+impl FromDicomValue for Vec<NaiveDate> {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("Vec<NaiveDate>".to_string()))?;
+        let v = trim_padding(std::str::from_utf8(data)?);
+        if v.is_empty() {
+            return Ok(vec![]);
+        }
+        v.split('\\')
+            .map(|date| Ok(NaiveDate::parse_from_str(date.trim(), "%Y%m%d")?))
+            .collect()
+    }
+}
+
+/// TM values are HHMMSS.FFFFFF where the minutes, seconds and fraction are optional. The
+/// older HH:MM:SS format is also accepted. Only the first value is read when the element is
+/// multi-valued.
+impl FromDicomValue for NaiveTime {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("NaiveTime".to_string()))?;
+        let first = std::str::from_utf8(data)?.split('\\').next().unwrap_or_default();
+        let repr = trim_padding(first).replace(':', "");
+        match parse_time(&repr) {
+            Some(time) if !repr.is_empty() => Ok(time),
+            _ => Err(DicomError::ParseTM(repr)),
+        }
+    }
+}
+
+/// Parse HHMMSS.FFFFFF where all the components are optional. Empty is midnight.
+fn parse_time(repr: &str) -> Option<NaiveTime> {
+    let (hms, fraction) = repr.split_once('.').unwrap_or((repr, ""));
+    if !hms.bytes().all(|b| b.is_ascii_digit())
+        || hms.len() % 2 == 1
+        || hms.len() > 6
+        || fraction.len() > 6
+    {
+        return None;
+    }
+    let mut components = [0u32; 3];
+    for (i, component) in components.iter_mut().take(hms.len() / 2).enumerate() {
+        *component = hms[2 * i..2 * i + 2].parse().ok()?;
+    }
+    let micro = if fraction.is_empty() {
+        0
+    } else {
+        let digits: u32 = fraction.parse().ok()?;
+        digits * 10u32.pow(6 - fraction.len() as u32)
+    };
+
+    NaiveTime::from_hms_micro_opt(components[0], components[1], components[2], micro)
+}
+
+/// DT values are YYYYMMDDHHMMSS.FFFFFF&ZZXX. The time components are optional and the timezone
+/// offset (&ZZXX) is ignored. Only the first value is read when the element is multi-valued.
+impl FromDicomValue for NaiveDateTime {
+    fn from_element(
+        el: &DataElement,
+        _transfer_syntax: &TransferSyntax,
+    ) -> Result<Self, DicomError> {
+        let data = el
+            .data
+            .as_bytes()
+            .ok_or_else(|| DicomError::ConvertTypeExpectBuf("NaiveDateTime".to_string()))?;
+        let first = std::str::from_utf8(data)?.split('\\').next().unwrap_or_default();
+        let repr = trim_padding(first);
+        let err = || DicomError::ParseDT(repr.to_string());
+
+        let without_offset = repr.split(['+', '-']).next().unwrap_or_default();
+        if without_offset.len() < 8 || !without_offset.is_char_boundary(8) {
+            return Err(err());
+        }
+        let (date, time) = without_offset.split_at(8);
+        let date = NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| err())?;
+        let time = parse_time(time).ok_or_else(err)?;
+        Ok(date.and_time(time))
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PersonName(pub Vec<String>);
 
+/// Sex of the patient (x0010x0040).
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Sex {
+    Male,
+    Female,
+    Other,
+}
+
+/// Image Type (x0008x0008). The first two values have a defined meaning, the other ones depend on
+/// the modality.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ImageType {
+    /// Pixel Data Characteristics: ORIGINAL or DERIVED.
+    pub pixel_data: String,
+    /// Patient Examination Characteristics: PRIMARY or SECONDARY.
+    pub patient_examination: String,
+    /// Modality specific characteristics, such as AXIAL or LOCALIZER for a CT.
+    pub other: Vec<String>,
+}
+
 impl FromDicomValue for PersonName {
     fn from_element(
         el: &DataElement,
         _transfer_syntax: &TransferSyntax,
     ) -> Result<Self, DicomError> {
-        if let Value::Buf(data) = el.data {
-            let v = std::str::from_utf8(data)?
-                .to_string()
+        if let Some(data) = el.data.as_bytes() {
+            let mut v = std::str::from_utf8(trim_padding_bytes(data))?
                 .split('^')
                 .map(|s| s.to_owned())
                 .collect::<Vec<_>>();
+            // Trailing empty components can be omitted. Empty components in the middle are kept
+            // so that each component stays at its position.
+            while v.last().is_some_and(|c| c.is_empty()) {
+                v.pop();
+            }
             Ok(PersonName(v))
         } else {
             Err(DicomError::ConvertTypeExpectBuf("PersonName".to_string()))
@@ -377,6 +1612,8 @@ impl FromDicomValue for PersonName {
 mod test {
     use super::*;
     use crate::tag::Tag;
+    use crate::test_helpers::{sequence, text_element, us_element, vr_element};
+
     #[test]
     fn parse_years() {
         let repr = "014Y";
@@ -509,6 +1746,734 @@ mod test {
         );
     }
 
+    #[test]
+    fn age_between_dates() {
+        let from = NaiveDate::from_ymd_opt(1990, 6, 15).unwrap();
+        assert_eq!(
+            Some(Age { age: 29, format: AgeFormat::Year }),
+            Age::between(from, NaiveDate::from_ymd_opt(2020, 6, 14).unwrap())
+        );
+        assert_eq!(
+            Some(Age { age: 3, format: AgeFormat::Month }),
+            Age::between(from, NaiveDate::from_ymd_opt(1990, 9, 20).unwrap())
+        );
+        assert_eq!(
+            Some(Age { age: 2, format: AgeFormat::Week }),
+            Age::between(from, NaiveDate::from_ymd_opt(1990, 7, 1).unwrap())
+        );
+        assert_eq!(
+            Some(Age { age: 3, format: AgeFormat::Day }),
+            Age::between(from, NaiveDate::from_ymd_opt(1990, 6, 18).unwrap())
+        );
+        assert_eq!(None, Age::between(from, NaiveDate::from_ymd_opt(1990, 6, 14).unwrap()));
+    }
+
+    #[test]
+    fn patient_age_from_dates() {
+        let birth_date = String::from("19900615");
+        let study_date = String::from("20200203");
+        let obj = DicomObject::new(
+            vec![
+                DataElement {
+                    tag: Tag::x0008x0020,
+                    length: 8,
+                    data: Value::Buf(study_date.as_bytes()),
+                    vr: None,
+                },
+                DataElement {
+                    tag: Tag::x0010x0030,
+                    length: 8,
+                    data: Value::Buf(birth_date.as_bytes()),
+                    vr: None,
+                },
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+
+        assert_eq!(
+            Some(Age { age: 29, format: AgeFormat::Year }),
+            obj.patient_age()
+        );
+    }
+
+    #[test]
+    fn jpeg2000_transfer_syntax() {
+        let lossless = Value::Buf(b"1.2.840.10008.1.2.4.90");
+        assert_eq!(
+            Some(CompressionScheme::Jpeg2000Lossless),
+            TransferSyntax::try_from(&lossless).unwrap().compression_scheme
+        );
+
+        let lossy = Value::Buf(b"1.2.840.10008.1.2.4.91");
+        assert_eq!(
+            Some(CompressionScheme::Jpeg2000Lossy),
+            TransferSyntax::try_from(&lossy).unwrap().compression_scheme
+        );
+    }
+
+    #[test]
+    fn encapsulated_transfer_syntax() {
+        assert!(!TransferSyntax::little_endian_explicit().is_encapsulated());
+        assert!(TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossless)
+            .is_encapsulated());
+    }
+
+    #[test]
+    fn instance_and_acquisition_numbers() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x0012, "3 "),
+                text_element(Tag::x0020x0013, "125 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some(125), obj.instance_number());
+        assert_eq!(Some(3), obj.acquisition_number());
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(None, obj.instance_number());
+    }
+
+    #[test]
+    fn stored_value_range() {
+        let unsigned = DicomObject::new(
+            vec![us_element(Tag::x0028x0101, 12), us_element(Tag::x0028x0103, 0)],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some((0, 4095)), unsigned.stored_value_range());
+
+        let signed = DicomObject::new(
+            vec![us_element(Tag::x0028x0101, 12), us_element(Tag::x0028x0103, 1)],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some((-2048, 2047)), signed.stored_value_range());
+    }
+
+    #[test]
+    fn element_index() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x0020, "20200203"),
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                text_element(Tag::x0020x0013, "1 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some(2), obj.element_index(Tag::x0020x0013));
+        assert_eq!(None, obj.element_index(Tag::x0020x0012));
+    }
+
+    #[test]
+    fn known_transfer_syntax() {
+        assert_eq!(
+            Some(KnownTransferSyntax::ImplicitLE),
+            TransferSyntax::little_endian_implicit().known()
+        );
+        assert_eq!(
+            Some(KnownTransferSyntax::ExplicitLE),
+            TransferSyntax::little_endian_explicit().known()
+        );
+        assert_eq!(
+            Some(KnownTransferSyntax::ExplicitBE),
+            TransferSyntax::big_endian_explicit().known()
+        );
+        assert_eq!(
+            Some(KnownTransferSyntax::Jpeg2000Lossless),
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossless).known()
+        );
+        assert_eq!(
+            Some(KnownTransferSyntax::Jpeg2000Lossy),
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossy).known()
+        );
+        assert_eq!(
+            Some(KnownTransferSyntax::Rle),
+            TransferSyntax::with_compression_scheme(CompressionScheme::Rle).known()
+        );
+    }
+
+    #[test]
+    fn image_position_and_orientation() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x0032, "-125.5\\-130\\ 42.25 "),
+                text_element(Tag::x0020x0037, "1\\0\\0\\0\\0.5\\-0.8660254 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!([-125.5, -130.0, 42.25], obj.image_position().unwrap());
+        assert_eq!(
+            [1.0, 0.0, 0.0, 0.0, 0.5, -0.8660254],
+            obj.image_orientation().unwrap()
+        );
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0020x0032, "1\\2")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert!(obj.image_position().is_err());
+        assert!(obj.image_orientation().is_err());
+    }
+
+    #[test]
+    fn pixel_geometry_from_functional_groups() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0028x0030, "0.5\\0.25"),
+                text_element(Tag::x0018x0050, "2 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(
+            Some(PixelGeometry {
+                pixel_spacing: [0.5, 0.25],
+                slice_thickness: Some(2.0)
+            }),
+            obj.pixel_geometry()
+        );
+
+        let pixel_measures = sequence(
+            Tag::x0028x9110,
+            vec![vec![
+                text_element(Tag::x0018x0050, "1.25"),
+                text_element(Tag::x0028x0030, "0.7\\0.8 "),
+            ]],
+        );
+        let obj = DicomObject::new(
+            vec![sequence(Tag::x5200x9229, vec![vec![pixel_measures]])],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(
+            Some(PixelGeometry {
+                pixel_spacing: [0.7, 0.8],
+                slice_thickness: Some(1.25)
+            }),
+            obj.pixel_geometry()
+        );
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(None, obj.pixel_geometry());
+    }
+
+    #[test]
+    fn spacing_between_slices() {
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0018x0088, "1.5 ")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some(1.5), obj.spacing_between_slices());
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(None, obj.spacing_between_slices());
+    }
+
+    #[test]
+    fn image_type() {
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0008x0008, "ORIGINAL\\PRIMARY\\AXIAL ")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        let image_type = obj.image_type().unwrap();
+        assert_eq!("ORIGINAL", image_type.pixel_data);
+        assert_eq!("PRIMARY", image_type.patient_examination);
+        assert_eq!(vec!["AXIAL".to_string()], image_type.other);
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0008x0008, "DERIVED")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(None, obj.image_type());
+    }
+
+    #[test]
+    fn patient_demographics() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0010x0040, "F "),
+                text_element(Tag::x0010x1020, "1.72"),
+                text_element(Tag::x0010x1030, "64.5 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some(Sex::Female), obj.patient_sex());
+        assert_eq!(Some(1.72), obj.patient_size());
+        assert_eq!(Some(64.5), obj.patient_weight());
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0010x0040, "")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(None, obj.patient_sex());
+        assert_eq!(None, obj.patient_size());
+        assert_eq!(None, obj.patient_weight());
+    }
+
+    #[test]
+    fn lossy_compression() {
+        let lossless = DicomObject::new(
+            vec![text_element(Tag::x0028x2110, "00")],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossless),
+        );
+        assert!(!lossless.is_lossy());
+
+        let lossy = DicomObject::new(
+            vec![],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Jpeg2000Lossy),
+        );
+        assert!(lossy.is_lossy());
+
+        // Decompressed after a lossy compression.
+        let decompressed = DicomObject::new(
+            vec![text_element(Tag::x0028x2110, "01")],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert!(decompressed.is_lossy());
+    }
+
+    #[test]
+    fn free_text() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x2111, "Averaged\\smoothed "),
+                text_element(Tag::x0020x4000, " Motion artifact"),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(
+            Some("Averaged\\smoothed".to_string()),
+            obj.derivation_description()
+        );
+        // The leading spaces of the free text are kept.
+        assert_eq!(Some(" Motion artifact".to_string()), obj.image_comments());
+
+        // ST and LT are single-valued.
+        let comments: Vec<String> = obj.try_get(Tag::x0008x2111).unwrap();
+        assert_eq!(1, comments.len());
+    }
+
+    #[test]
+    fn to_btreemap() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                us_element(Tag::x0028x0010, 512),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        let map = obj.to_btreemap();
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&"DOE^JOHN".to_string()), map.get("0010,0010"));
+        assert_eq!(Some(&"512".to_string()), map.get("0028,0010"));
+    }
+
+    #[test]
+    fn summary() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                us_element(Tag::x0028x0010, 512),
+                DataElement {
+                    tag: Tag::x0008x1030,
+                    vr: None,
+                    length: 100,
+                    data: Value::Owned(vec![b'A'; 100]),
+                },
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        let summary = obj.summary();
+        assert_eq!(obj.elements().len(), summary.len());
+        assert_eq!(
+            ElementSummary {
+                tag: Tag::x0028x0010,
+                vr: Some(ValueRepresentation::US),
+                length: 2,
+                preview: "512".to_string(),
+            },
+            summary[1]
+        );
+        assert_eq!(PREVIEW_LEN + 3, summary[2].preview.len());
+    }
+
+    #[test]
+    fn content_datetime() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x0023, "20200131"),
+                text_element(Tag::x0008x0033, "142501.25 "),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap().and_hms_micro_opt(14, 25, 1, 250_000).unwrap()),
+            obj.content_datetime()
+        );
+        assert_eq!(None, obj.series_datetime());
+    }
+
+    #[test]
+    fn series_datetime() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x0021, "19991231"),
+                text_element(Tag::x0008x0031, "23:59"),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(1999, 12, 31).unwrap().and_hms_opt(23, 59, 0).unwrap()),
+            obj.series_datetime()
+        );
+
+        // Without time, the series starts at midnight. An invalid time is an error.
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0008x0021, "19991231")],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(1999, 12, 31).unwrap().and_hms_opt(0, 0, 0).unwrap()),
+            obj.series_datetime()
+        );
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x0021, "19991231"),
+                text_element(Tag::x0008x0031, "2561"),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(None, obj.series_datetime());
+    }
+
+    #[test]
+    fn series_key() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x000D, "1.2.840.1\0"),
+                text_element(Tag::x0020x000E, "1.2.840.1.2"),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        let mut series = std::collections::HashMap::new();
+        series.entry(obj.series_key().unwrap()).or_insert_with(Vec::new).push(1);
+        assert_eq!(
+            Some(&vec![1]),
+            series.get(&("1.2.840.1".to_string(), "1.2.840.1.2".to_string()))
+        );
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0020x000D, "1.2.840.1\0")],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(None, obj.series_key());
+    }
+
+    #[test]
+    fn concatenation() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x9161, "1.2.840.5\0"),
+                us_element(Tag::x0020x9162, 2),
+                DataElement {
+                    tag: Tag::x0020x9228,
+                    length: 4,
+                    data: Value::Buf(&[0x20, 0x00, 0x00, 0x00]),
+                    vr: None,
+                },
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some("1.2.840.5".to_string()), obj.concatenation_uid());
+        assert_eq!(Some(2), obj.in_concatenation_number());
+        assert_eq!(Some(32), obj.concatenation_frame_offset());
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(None, obj.concatenation_uid());
+        assert_eq!(None, obj.in_concatenation_number());
+    }
+
+    #[test]
+    fn functional_group_value() {
+        let echo = |time: f64| {
+            sequence(
+                Tag::x0018x9114,
+                vec![vec![vr_element(
+                    Tag::x0018x9082,
+                    ValueRepresentation::FD,
+                    time.to_le_bytes().to_vec(),
+                )]],
+            )
+        };
+        let obj = DicomObject::new(
+            vec![
+                sequence(Tag::x5200x9229, vec![vec![echo(80.0)]]),
+                sequence(Tag::x5200x9230, vec![vec![echo(12.5)], vec![]]),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+
+        let echo_time =
+            |frame| obj.functional_group_value::<f64>(frame, Tag::x0018x9114, Tag::x0018x9082);
+        assert_eq!(12.5, echo_time(0).unwrap());
+        // The second frame has no echo sequence, the shared value is used.
+        assert_eq!(80.0, echo_time(1).unwrap());
+        assert!(matches!(
+            obj.functional_group_value::<f64>(0, Tag::x0028x9110, Tag::x0028x0030),
+            Err(DicomError::NoSuchTag(Tag::x0028x0030))
+        ));
+    }
+
+    #[test]
+    fn frame_stack_position() {
+        let frame_content = |stack_id: &'static str, position: u32| {
+            let stack = vec![
+                text_element(Tag::x0020x9056, stack_id),
+                vr_element(
+                    Tag::x0020x9057,
+                    ValueRepresentation::UL,
+                    position.to_le_bytes().to_vec(),
+                ),
+            ];
+            sequence(
+                Tag::x5200x9230,
+                vec![vec![sequence(Tag::x0020x9111, vec![stack])]],
+            )
+        };
+        let obj = DicomObject::new(
+            vec![frame_content("1 ", 3)],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(Some(("1".to_string(), 3)), obj.frame_stack_position(0));
+        assert_eq!(None, obj.frame_stack_position(1));
+    }
+
+    #[test]
+    fn sequences() {
+        let contours = sequence(
+            Tag::x3006x0039,
+            vec![
+                vec![sequence(Tag::x3006x0040, vec![vec![], vec![], vec![]])],
+                vec![sequence(Tag::x3006x0040, vec![])],
+            ],
+        );
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0010x0010, "DOE^JOHN"),
+                contours,
+                sequence(Tag::x0028x3000, vec![vec![]]),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+
+        assert_eq!(
+            vec![
+                (vec![Tag::x3006x0039], 2),
+                (vec![Tag::x3006x0039, Tag::x3006x0040], 3),
+                (vec![Tag::x3006x0039, Tag::x3006x0040], 0),
+                (vec![Tag::x0028x3000], 1),
+            ],
+            obj.sequences()
+        );
+    }
+
+    #[test]
+    fn group() {
+        let obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0020x0013, 1),
+                us_element(Tag::x0028x0010, 512),
+                us_element(Tag::UNKNOWN(0x0028, 0x9999), 7),
+                us_element(Tag::x0028x0011, 256),
+                us_element(Tag::UNKNOWN(0x0029, 0x0010), 1),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+
+        let tags: Vec<Tag> = obj.group(0x0028).iter().map(|el| el.tag).collect();
+        assert_eq!(
+            vec![Tag::x0028x0010, Tag::UNKNOWN(0x0028, 0x9999), Tag::x0028x0011],
+            tags
+        );
+        assert!(obj.group(0x0010).is_empty());
+    }
+
+    #[test]
+    fn from_el_multi_valued_is() {
+        let is = |value| -> Result<Vec<i32>, _> {
+            FromDicomValue::from_element(
+                &text_element(Tag::x0028x0008, value),
+                &TransferSyntax::little_endian_explicit(),
+            )
+        };
+        assert_eq!(vec![1, 2, 3], is("1\\2\\3").unwrap());
+        assert_eq!(vec![42], is(" 42 ").unwrap());
+        assert_eq!(vec![-7], is("-7").unwrap());
+        assert!(matches!(is("1\\2.5"), Err(DicomError::ParseIS(_, v)) if v == "2.5"));
+    }
+
+    #[test]
+    fn from_el_multi_valued_ds() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x0037, "1\\0\\0 \\0\\-0.5\\+8.66e-1 "),
+                text_element(Tag::x0028x1050, ""),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(
+            vec![1.0, 0.0, 0.0, 0.0, -0.5, 0.866],
+            obj.get::<Vec<f64>>(Tag::x0020x0037)
+        );
+        assert!(obj.get::<Vec<f64>>(Tag::x0028x1050).is_empty());
+    }
+
+    #[test]
+    fn read_other_double() {
+        let values = [1.5f64, -2.0, 1e-3];
+        for (ts, bytes) in [
+            (
+                TransferSyntax::little_endian_explicit(),
+                values.iter().flat_map(|v| v.to_le_bytes().to_vec()).collect::<Vec<_>>(),
+            ),
+            (
+                TransferSyntax::big_endian_explicit(),
+                values.iter().flat_map(|v| v.to_be_bytes().to_vec()).collect(),
+            ),
+        ] {
+            let mut el = DataElement {
+                tag: Tag::UNKNOWN(0x0009, 0x1010),
+                vr: Some(ValueRepresentation::OD),
+                length: 24,
+                data: Value::Owned(bytes),
+            };
+            let parsed: Vec<f64> = FromDicomValue::from_element(&el, &ts).unwrap();
+            assert_eq!(values.to_vec(), parsed);
+
+            if let Value::Owned(ref mut bytes) = el.data {
+                bytes.pop();
+            }
+            let parsed: DicomResult<Vec<f64>> = FromDicomValue::from_element(&el, &ts);
+            assert!(parsed.is_err());
+        }
+    }
+
+    #[test]
+    fn slice_normal() {
+        // Sagittal slice: rows along y, columns along -z.
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0020x0032, "12.5\\-100\\100 "),
+                text_element(Tag::x0020x0037, "0\\1\\0\\0\\0\\-1 "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(Some([-1.0, 0.0, 0.0]), obj.slice_normal());
+        assert_eq!(Some(-12.5), obj.projected_position());
+
+        let obj = DicomObject::new(vec![], TransferSyntax::little_endian_implicit());
+        assert_eq!(None, obj.slice_normal());
+        assert_eq!(None, obj.projected_position());
+    }
+
+    #[test]
+    fn expected_pixel_bytes() {
+        let mut obj = DicomObject::new(
+            vec![
+                us_element(Tag::x0028x0002, 3),
+                text_element(Tag::x0028x0008, "3 "),
+                us_element(Tag::x0028x0010, 4),
+                us_element(Tag::x0028x0011, 5),
+                us_element(Tag::x0028x0100, 8),
+                us_element(Tag::x0028x0101, 8),
+            ],
+            TransferSyntax::little_endian_explicit(),
+        );
+        assert_eq!(Some(4 * 5 * 3 * 3), obj.expected_pixel_bytes());
+
+        obj.elements.retain(|el| el.tag != Tag::x0028x0010);
+        assert_eq!(None, obj.expected_pixel_bytes());
+    }
+
+    #[test]
+    fn number_of_frames_from_offset_table() {
+        let mut obj = DicomObject::new(
+            vec![],
+            TransferSyntax::with_compression_scheme(CompressionScheme::Rle),
+        );
+        assert_eq!(1, obj.number_of_frames());
+
+        obj.pixel_data = Some(Cow::Owned(vec![
+            0xFE, 0xFF, 0x00, 0xE0, 0x0C, 0x00, 0x00, 0x00, // Basic Offset Table with 3 frames
+            0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00,
+            0xFE, 0xFF, 0x00, 0xE0, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02,
+            0xFE, 0xFF, 0x00, 0xE0, 0x02, 0x00, 0x00, 0x00, 0x03, 0x04,
+            0xFE, 0xFF, 0x00, 0xE0, 0x02, 0x00, 0x00, 0x00, 0x05, 0x06,
+            0xFE, 0xFF, 0xDD, 0xE0, 0x00, 0x00, 0x00, 0x00,
+        ]));
+        assert_eq!(3, obj.number_of_frames());
+
+        obj.append(vec![text_element(Tag::x0028x0008, "2 ")]);
+        assert_eq!(2, obj.number_of_frames());
+    }
+
+    #[test]
+    fn text_multiplicity() {
+        let ts = TransferSyntax::little_endian_explicit();
+        let mut el = text_element(Tag::UNKNOWN(0x0009, 0x0010), "C:\\dicom\\a.dcm ");
+        el.vr = Some(ValueRepresentation::UT);
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["C:\\dicom\\a.dcm".to_string()], values);
+
+        el.vr = Some(ValueRepresentation::UC);
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["C:", "dicom", "a.dcm"], values);
+
+        // Empty values are kept.
+        let el = text_element(Tag::x0008x1030, "HEAD\\\\NECK ");
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["HEAD", "", "NECK"], values);
+
+        // Implicit VR, UC from the dictionary.
+        let el = text_element(Tag::x0008x0119, "LONG-CODE-1\\LONG-CODE-2 ");
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["LONG-CODE-1", "LONG-CODE-2"], values);
+    }
+
+    #[test]
+    fn backslash_is_never_escaped() {
+        // What would be an escaped backslash elsewhere is two delimiters around an empty value.
+        let ts = TransferSyntax::little_endian_explicit();
+        let el = vr_element(Tag::x0008x1030, ValueRepresentation::LO, b"A\\\\B ".to_vec());
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["A", "", "B"], values);
+
+        // The text VRs are single-valued so their backslashes are kept.
+        let el = vr_element(Tag::x0008x1030, ValueRepresentation::LT, b"A\\\\B ".to_vec());
+        let values: Vec<String> = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(vec!["A\\\\B"], values);
+    }
+
+    #[test]
+    fn display_string() {
+        let ts = TransferSyntax::little_endian_explicit();
+        let us = DataElement {
+            tag: Tag::x0028x0010,
+            length: 4,
+            data: Value::Owned(vec![0x00, 0x02, 0x01, 0x00]),
+            vr: Some(ValueRepresentation::US),
+        };
+        assert_eq!("512\\1", us.to_display_string(&ts));
+
+        let ob = DataElement {
+            tag: Tag::x7FE0x0010,
+            length: 3,
+            data: Value::Owned(vec![0x00, 0xAB, 0x10]),
+            vr: Some(ValueRepresentation::OB),
+        };
+        assert_eq!("00AB10", ob.to_display_string(&ts));
+
+        let text = text_element(Tag::x0010x0010, "DOE^JOHN  ");
+        assert_eq!("DOE^JOHN", text.to_display_string(&ts));
+    }
+
     #[test]
     fn from_el_u16() {
         let bytes: Vec<u8> = vec![8,0];
@@ -524,6 +2489,187 @@ mod test {
         assert_eq!(8, v.unwrap());
     }
 
+    #[test]
+    fn from_el_ss() {
+        let el = DataElement {
+            tag: Tag::x0028x0120,
+            length: 2,
+            data: Value::Buf(&[0xFF, 0xFF]),
+            vr: Some(ValueRepresentation::SS),
+        };
+        let v: i16 =
+            FromDicomValue::from_element(&el, &TransferSyntax::little_endian_explicit()).unwrap();
+        assert_eq!(-1, v);
+        let el = DataElement { data: Value::Buf(&[0xFF, 0xFE]), ..el };
+        let v: i16 =
+            FromDicomValue::from_element(&el, &TransferSyntax::big_endian_explicit()).unwrap();
+        assert_eq!(-2, v);
+    }
+
+    #[test]
+    fn from_el_ul() {
+        let ul_element = |data: &'static [u8]| DataElement {
+            tag: Tag::x0004x1400,
+            length: 4,
+            data: Value::Buf(data),
+            vr: Some(ValueRepresentation::UL),
+        };
+
+        let little: u32 = FromDicomValue::from_element(
+            &ul_element(&[0x02, 0x00, 0x00, 0x00]),
+            &TransferSyntax::little_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(2, little);
+        let big: u32 = FromDicomValue::from_element(
+            &ul_element(&[0x00, 0x00, 0x00, 0x02]),
+            &TransferSyntax::big_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(2, big);
+
+        let obj = DicomObject::new(
+            vec![text_element(Tag::x0028x0008, "2 ")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(2, obj.get::<u32>(Tag::x0028x0008));
+    }
+
+    #[test]
+    fn from_el_floats() {
+        let float = |vr, data: Vec<u8>| DataElement {
+            tag: Tag::x0018x9082,
+            length: data.len() as u32,
+            data: Value::Owned(data),
+            vr: Some(vr),
+        };
+        let little = TransferSyntax::little_endian_explicit();
+        let big = TransferSyntax::big_endian_explicit();
+
+        let fl = -1.5e-3f32;
+        let le = float(ValueRepresentation::FL, fl.to_le_bytes().to_vec());
+        let be = float(ValueRepresentation::FL, fl.to_be_bytes().to_vec());
+        let v: f32 = FromDicomValue::from_element(&le, &little).unwrap();
+        assert_eq!(fl.to_bits(), v.to_bits());
+        let v: f32 = FromDicomValue::from_element(&be, &big).unwrap();
+        assert_eq!(fl.to_bits(), v.to_bits());
+
+        let fd = std::f64::consts::PI;
+        let le = float(ValueRepresentation::FD, fd.to_le_bytes().to_vec());
+        let be = float(ValueRepresentation::FD, fd.to_be_bytes().to_vec());
+        let v: f64 = FromDicomValue::from_element(&le, &little).unwrap();
+        assert_eq!(fd.to_bits(), v.to_bits());
+        let v: f64 = FromDicomValue::from_element(&be, &big).unwrap();
+        assert_eq!(fd.to_bits(), v.to_bits());
+    }
+
+    #[test]
+    fn from_el_ds() {
+        let ds = |value| -> Result<f64, _> {
+            FromDicomValue::from_element(
+                &text_element(Tag::x0028x1050, value),
+                &TransferSyntax::little_endian_explicit(),
+            )
+        };
+        assert_eq!(-120.5, ds("  -120.5 ").unwrap());
+        assert_eq!(0.0, ds("0").unwrap());
+        assert_eq!(40.0, ds("40\\400 ").unwrap());
+        assert!(matches!(ds("12.3.4"), Err(DicomError::ParseDS(_, v)) if v == "12.3.4"));
+    }
+
+    #[test]
+    fn set_elements() {
+        let mut obj = DicomObject::new(
+            vec![text_element(Tag::x0010x0010, "DOE^JOHN")],
+            TransferSyntax::little_endian_implicit(),
+        );
+        obj.set_element(DataElement {
+            tag: Tag::x0020x4000,
+            vr: None,
+            length: 4,
+            data: Value::Owned(b"NEW ".to_vec()),
+        });
+        assert_eq!("NEW ", obj.try_get::<String>(Tag::x0020x4000).unwrap());
+
+        obj.set_element(text_element(Tag::x0010x0010, "ANONYMOUS "));
+        assert_eq!(2, obj.elements.len());
+        obj.set_element(text_element(Tag::x0008x0020, "20200203"));
+        obj.set_element(text_element(Tag::x0010x0030, "19700101"));
+        let tags: Vec<Tag> = obj.elements.iter().map(|el| el.tag).collect();
+        assert_eq!(
+            vec![
+                Tag::x0008x0020,
+                Tag::x0010x0010,
+                Tag::x0010x0030,
+                Tag::x0020x4000
+            ],
+            tags
+        );
+        assert_eq!("ANONYMOUS ", obj.try_get::<String>(Tag::x0010x0010).unwrap());
+
+        obj.get_element_mut(Tag::x0010x0010).unwrap().data = Value::Owned(vec![]);
+        assert_eq!("", obj.try_get::<String>(Tag::x0010x0010).unwrap());
+    }
+
+    #[test]
+    fn read_private_element() {
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::UNKNOWN(0x0009, 0x0010), "ACME 1.0"),
+                text_element(Tag::UNKNOWN(0x0009, 0x0011), "OTHER "),
+                text_element(Tag::UNKNOWN(0x0009, 0x1001), "ACME VALUE"),
+                text_element(Tag::UNKNOWN(0x0009, 0x1101), "OTHER VALUE "),
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+
+        let el = obj.private_element(0x0009, "ACME 1.0", 0x01).unwrap();
+        assert_eq!(Tag::UNKNOWN(0x0009, 0x1001), el.tag);
+        let el = obj.private_element(0x0009, "OTHER", 0x01).unwrap();
+        assert_eq!(Tag::UNKNOWN(0x0009, 0x1101), el.tag);
+        assert!(obj.private_element(0x0009, "ACME 1.0", 0x02).is_none());
+        assert!(obj.private_element(0x0009, "UNKNOWN", 0x01).is_none());
+    }
+
+    #[test]
+    fn from_el_sl() {
+        let sl_element = |data: &'static [u8]| DataElement {
+            tag: Tag::UNKNOWN(0x0009, 0x1001),
+            length: data.len() as u32,
+            data: Value::Buf(data),
+            vr: Some(ValueRepresentation::SL),
+        };
+
+        let little: i32 = FromDicomValue::from_element(
+            &sl_element(&[0xFE, 0xFF, 0xFF, 0xFF]),
+            &TransferSyntax::little_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(-2, little);
+        let big: i32 = FromDicomValue::from_element(
+            &sl_element(&[0x00, 0x01, 0x00, 0x02]),
+            &TransferSyntax::big_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(0x0001_0002, big);
+
+        let short: Result<i32, _> = FromDicomValue::from_element(
+            &sl_element(&[0x01, 0x00]),
+            &TransferSyntax::little_endian_explicit(),
+        );
+        assert!(matches!(short, Err(DicomError::IoError(_))));
+
+        let sequence = DataElement {
+            tag: Tag::UNKNOWN(0x0009, 0x1001),
+            length: 0,
+            data: Value::Sequence(vec![]),
+            vr: Some(ValueRepresentation::SQ),
+        };
+        let res: Result<i32, _> =
+            FromDicomValue::from_element(&sequence, &TransferSyntax::little_endian_explicit());
+        assert!(matches!(res, Err(DicomError::ConvertTypeExpectBuf(_))));
+    }
+
     #[test]
     fn from_el_age() {
         let age = Age {
@@ -544,9 +2690,94 @@ mod test {
         assert_eq!(age, v.unwrap());
     }
 
+    #[test]
+    fn from_el_multiple_ages() {
+        let ages: Vec<Age> = FromDicomValue::from_element(
+            &text_element(Tag::x0010x1010, "014Y\\030D"),
+            &TransferSyntax::little_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                Age { age: 14, format: AgeFormat::Year },
+                Age { age: 30, format: AgeFormat::Day },
+            ],
+            ages
+        );
+
+        let res: Result<Vec<Age>, _> = FromDicomValue::from_element(
+            &text_element(Tag::x0010x1010, "014Y\\30D"),
+            &TransferSyntax::little_endian_explicit(),
+        );
+        assert!(matches!(res, Err(DicomError::ParseAS(msg)) if msg.starts_with("Component 1")));
+    }
+
+    #[test]
+    fn from_el_multiple_dates() {
+        let dates: Vec<NaiveDate> = FromDicomValue::from_element(
+            &text_element(Tag::x0008x0020, "20200131\\19991231"),
+            &TransferSyntax::little_endian_explicit(),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                NaiveDate::from_ymd_opt(2020, 1, 31).unwrap(),
+                NaiveDate::from_ymd_opt(1999, 12, 31).unwrap(),
+            ],
+            dates
+        );
+
+        let res: Result<Vec<NaiveDate>, _> = FromDicomValue::from_element(
+            &text_element(Tag::x0008x0020, "20200131\\2020"),
+            &TransferSyntax::little_endian_explicit(),
+        );
+        assert!(matches!(res, Err(DicomError::ParseDA(_))));
+    }
+
+    #[test]
+    fn from_el_time() {
+        let tm = |value| -> Result<NaiveTime, _> {
+            FromDicomValue::from_element(
+                &text_element(Tag::x0008x0033, value),
+                &TransferSyntax::little_endian_explicit(),
+            )
+        };
+        assert_eq!(NaiveTime::from_hms_opt(13, 0, 0).unwrap(), tm("13").unwrap());
+        assert_eq!(NaiveTime::from_hms_opt(13, 45, 0).unwrap(), tm("1345").unwrap());
+        assert_eq!(NaiveTime::from_hms_opt(13, 45, 30).unwrap(), tm("134530").unwrap());
+        assert_eq!(
+            NaiveTime::from_hms_milli_opt(13, 45, 30, 250).unwrap(),
+            tm("134530.250").unwrap()
+        );
+        assert_eq!(NaiveTime::from_hms_opt(8, 0, 0).unwrap(), tm("08\\0930 ").unwrap());
+        assert!(matches!(tm("1345301"), Err(DicomError::ParseTM(_))));
+        assert!(matches!(tm("13h45"), Err(DicomError::ParseTM(_))));
+        assert!(matches!(tm("1é3"), Err(DicomError::ParseTM(_))));
+    }
+
+    #[test]
+    fn from_el_datetime() {
+        let dt = |value| -> Result<NaiveDateTime, _> {
+            FromDicomValue::from_element(
+                &text_element(Tag::x0008x002A, value),
+                &TransferSyntax::little_endian_explicit(),
+            )
+        };
+        let date = NaiveDate::from_ymd_opt(2020, 2, 3).unwrap();
+        assert_eq!(date.and_hms_opt(13, 45, 30).unwrap(), dt("20200203134530").unwrap());
+        assert_eq!(date.and_hms_opt(13, 45, 0).unwrap(), dt("202002031345").unwrap());
+        assert_eq!(
+            date.and_hms_micro_opt(13, 45, 30, 500_000).unwrap(),
+            dt("20200203134530.5-0500 ").unwrap()
+        );
+        assert_eq!(date.and_hms_opt(0, 0, 0).unwrap(), dt("20200203").unwrap());
+        assert!(matches!(dt("2020"), Err(DicomError::ParseDT(_))));
+        assert!(matches!(dt("20200203134"), Err(DicomError::ParseDT(_))));
+    }
+
     #[test]
     fn from_el_date() {
-        let date = NaiveDate::from_ymd(2020, 2, 3);
+        let date = NaiveDate::from_ymd_opt(2020, 2, 3).unwrap();
         let date_bytes = String::from("20200203");
         let el = DataElement {
             tag: Tag::x0002x0010,
@@ -578,6 +2809,53 @@ mod test {
         assert_eq!(expected, v.unwrap());
     }
 
+    #[test]
+    fn from_el_name_trailing_carets() {
+        let ts = TransferSyntax::little_endian_implicit();
+        let el = text_element(Tag::x0010x0010, "Smith^John^^^");
+        let v: PersonName = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(PersonName(vec!["Smith".to_owned(), "John".to_owned()]), v);
+
+        let el = text_element(Tag::x0010x0010, "Smith^^Paul");
+        let v: PersonName = FromDicomValue::from_element(&el, &ts).unwrap();
+        assert_eq!(
+            PersonName(vec!["Smith".to_owned(), "".to_owned(), "Paul".to_owned()]),
+            v
+        );
+    }
+
+    #[test]
+    fn from_el_name_multibyte_padded() {
+        let expected = PersonName(vec!["Müller".to_owned(), "Jürgen".to_owned()]);
+        // 15 bytes but 13 characters, padded to an even length.
+        let el = text_element(Tag::x0010x0010, "Müller^Jürgen ");
+        assert_eq!(16, el.length);
+
+        let v: Result<PersonName, _> =
+            FromDicomValue::from_element(&el, &TransferSyntax::little_endian_implicit());
+        assert_eq!(expected, v.unwrap());
+    }
+
+    #[test]
+    fn text_latin1_padded() {
+        // "Müller" in Latin-1 (7 bytes), padded.
+        let name: &'static [u8] = &[0x4D, 0xFC, 0x6C, 0x6C, 0x65, 0x72, 0x20, 0x20];
+        let obj = DicomObject::new(
+            vec![
+                text_element(Tag::x0008x0005, "ISO_IR 100"),
+                DataElement {
+                    tag: Tag::x0010x0010,
+                    length: name.len() as u32,
+                    data: Value::Buf(name),
+                    vr: None,
+                },
+            ],
+            TransferSyntax::little_endian_implicit(),
+        );
+        assert_eq!(CharacterSet::Latin1, obj.character_set());
+        assert_eq!("Müller", obj.text(Tag::x0010x0010).unwrap());
+    }
+
     #[test]
     fn from_el_is_positivewithplus() {
         let expected = 10i32;
@@ -614,6 +2892,17 @@ mod test {
     }
 
 
+    #[test]
+    fn from_el_is_decimal() {
+        let el = text_element(Tag::x0020x0013, "12.5");
+        let v: Result<i32, _> =
+            FromDicomValue::from_element(&el, &TransferSyntax::little_endian_implicit());
+        assert_eq!(
+            "Cannot parse IS of Instance Number to integer = \"12.5\"",
+            format!("{}", v.unwrap_err())
+        );
+    }
+
     #[test]
     fn from_el_is_negative() {
         let expected = -10i32;
@@ -46,6 +46,16 @@ macro_rules! vr {
                    }).map_err(|e| e.into())
             }
 
+            /// Return the two characters of the VR, as written in explicit VR.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(
+                        ValueRepresentation::$name => $repr,
+                    )+
+                    ValueRepresentation::UNKNOWN(ref x) => x,
+                }
+            }
+
             pub fn has_special_length(&self) -> bool {
                 match self {
                     $(
@@ -85,6 +95,14 @@ vr! {
     (PN, "PN", "Person Name", false),
     (AS, "AS", "Age String", false),
     (SL, "SL", "Signed Long", false),
+    (SS, "SS", "Signed Short", false),
+    (IS, "IS", "Integer String", false),
+    (TM, "TM", "Time", false),
+    (LT, "LT", "Long Text", false),
+    (AE, "AE", "Application Entity", false),
+    (AT, "AT", "Attribute Tag", false),
+    (FL, "FL", "Floating Point Single", false),
+    (FD, "FD", "Floating Point Double", false),
 
     // Special length parsing
     (OB, "OB", "Other byte", true),
@@ -101,3 +119,32 @@ vr! {
     (UN, "UN", "Unknown", true),
     (UV, "UV", "Unsigned 64-bits very long", true)
 }
+
+impl ValueRepresentation {
+    /// Size of the header (tag, VR and length) of an element with this VR in explicit VR. The
+    /// special VRs have 2 bytes of padding and a 4 bytes length.
+    pub fn header_len(&self) -> usize {
+        if self.has_special_length() {
+            12
+        } else {
+            8
+        }
+    }
+
+    /// Size of the header (tag and length) of any element in implicit VR.
+    pub fn implicit_header_len() -> usize {
+        8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_len() {
+        assert_eq!(8, ValueRepresentation::US.header_len());
+        assert_eq!(12, ValueRepresentation::OB.header_len());
+        assert_eq!(8, ValueRepresentation::implicit_header_len());
+    }
+}
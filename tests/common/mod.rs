@@ -0,0 +1,47 @@
+//! Helpers to write small DICOM files for the integration tests. The unit tests of the crate
+//! include this module too.
+use std::fs;
+use std::path::PathBuf;
+
+/// Encode one element in explicit VR little endian.
+pub fn explicit_le(group: u16, element: u16, vr: &str, value: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&group.to_le_bytes());
+    bytes.extend_from_slice(&element.to_le_bytes());
+    bytes.extend_from_slice(vr.as_bytes());
+    if ["OB", "OW", "SQ", "UN", "UR", "UT"].contains(&vr) {
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    } else {
+        bytes.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    }
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// A small 2x2 8 bits explicit VR little endian file. `elements` are added after the patient
+/// name and must be sorted.
+pub fn fixture(patient_name: &str, elements: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = vec![0; 128];
+    bytes.extend_from_slice(b"DICM");
+    bytes.extend(explicit_le(0x0002, 0x0010, "UI", b"1.2.840.10008.1.2.1\0"));
+    bytes.extend(explicit_le(0x0010, 0x0010, "PN", patient_name.as_bytes()));
+    for element in elements {
+        bytes.extend_from_slice(element);
+    }
+    bytes.extend(explicit_le(0x0028, 0x0002, "US", &1u16.to_le_bytes()));
+    bytes.extend(explicit_le(0x0028, 0x0010, "US", &2u16.to_le_bytes()));
+    bytes.extend(explicit_le(0x0028, 0x0011, "US", &2u16.to_le_bytes()));
+    bytes.extend(explicit_le(0x0028, 0x0100, "US", &8u16.to_le_bytes()));
+    bytes.extend(explicit_le(0x0028, 0x0101, "US", &8u16.to_le_bytes()));
+    bytes.extend(explicit_le(0x7FE0, 0x0010, "OB", &[0, 64, 128, 255]));
+    bytes
+}
+
+/// Create an empty directory in the temporary folder.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dicom-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
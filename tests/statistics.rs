@@ -0,0 +1,39 @@
+mod common;
+
+use common::{explicit_le, fixture, temp_dir};
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn dump_tags_as_csv() {
+    let dir = temp_dir("statistics");
+    let instance_number = explicit_le(0x0020, 0x0013, "IS", b"7 ");
+    fs::write(dir.join("first.dcm"), fixture("DOE^JOHN", &[instance_number])).unwrap();
+    fs::write(dir.join("second.dcm"), fixture("DOE, JANE", &[])).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_statistics"))
+        .arg(&dir)
+        .arg("Patient Name")
+        .arg("0028,0010")
+        .arg("0020,0013")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines: Vec<_> = stdout.lines().collect();
+    assert_eq!("file,Patient Name,\"0028,0010\",\"0020,0013\"", lines.remove(0));
+    lines.sort();
+
+    let first = dir.join("first.dcm");
+    let second = dir.join("second.dcm");
+    assert_eq!(
+        vec![
+            format!("{},DOE^JOHN,2,7", first.display()),
+            format!("{},\"DOE, JANE\",2,", second.display()),
+        ],
+        lines
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}